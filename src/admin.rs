@@ -0,0 +1,453 @@
+//! Operator endpoints under `/admin`, authenticated with the `ADMIN_API_TOKEN` bearer token.
+
+use crate::{
+    asana::{self, dates::DateTime},
+    error::Error,
+    mpsc::{PausedResources, TxMessage},
+    return_json,
+    webhooks::{
+        self,
+        deliveries::{DeliveryFilter, DeliveryStatus},
+        monitor::DeliveryTracker,
+        queue::DeliveryQueue,
+        reconciler::Reconciler,
+        secrets::WebhookSecrets,
+    },
+    AppConfig,
+};
+use axum::{
+    extract::{Extension, Path, Query},
+    http::{header, Request},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post, Router},
+};
+use hyper::StatusCode;
+use mongodb::bson;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{collections::BTreeSet, sync::Arc};
+use subtle::ConstantTimeEq;
+use tokio::sync::mpsc;
+
+const DEFAULT_EVENTS_LIMIT: usize = 50;
+const MAX_EVENTS_LIMIT: usize = 500;
+
+/// Shared state for the admin handlers.
+#[derive(Clone, Default)]
+pub struct AdminState {
+    /// `None` when no Asana token is configured.
+    pub client: Option<Arc<asana::Client>>,
+    /// `None` when no `WEBHOOKS_CONFIG` or Asana token is configured.
+    pub reconciler: Option<Arc<Reconciler<asana::Client>>>,
+    pub paused: PausedResources,
+}
+
+pub fn admin_router() -> Router {
+    Router::new()
+        .route("/projects", get(handle_projects_get))
+        .route("/projects/:gid/resync", post(handle_project_resync_post))
+        .route("/projects/:gid/pause", post(handle_project_pause_post))
+        .route("/projects/:gid/resume", post(handle_project_resume_post))
+        .route("/webhooks", get(handle_webhooks_get))
+        .route("/webhooks/:gid", delete(handle_webhook_delete))
+        .route("/webhooks/reconcile", post(handle_reconcile_post))
+        .route("/events", get(handle_events_get))
+        .route_layer(middleware::from_fn(require_admin_token))
+}
+
+async fn require_admin_token<B>(
+    Extension(config): Extension<AppConfig>,
+    req: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let provided = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    let authorized = match (config.admin_token(), provided) {
+        (Some(expected), Some(provided)) => expected.as_bytes().ct_eq(provided.as_bytes()).into(),
+        _ => false,
+    };
+    if !authorized {
+        tracing::warn!(target: "security", "Rejected admin request to {}", req.uri().path());
+
+        return (
+            StatusCode::UNAUTHORIZED,
+            axum::Json(json!({ "status": "error: unauthorized" })),
+        )
+            .into_response();
+    }
+
+    next.run(req).await
+}
+
+fn not_configured(what: &str) -> Result<Response, Error> {
+    Ok(return_json(
+        json!({ "status": format!("error: {} is not configured", what) }),
+        Some(StatusCode::NOT_FOUND),
+    )?
+    .into_response())
+}
+
+fn bad_request(reason: String) -> Result<Response, Error> {
+    Ok(return_json(
+        json!({ "status": format!("error: {}", reason) }),
+        Some(StatusCode::BAD_REQUEST),
+    )?
+    .into_response())
+}
+
+#[derive(Debug, Serialize)]
+struct ProjectStatus {
+    gid: String,
+    /// Listed in `WEBHOOKS_CONFIG`.
+    watched: bool,
+    webhook_gid: Option<String>,
+    paused: bool,
+    /// `None` if nothing has been delivered since startup.
+    seconds_since_last_delivery: Option<u64>,
+}
+
+/// Projects that are configured or have a webhook secret on record. `pauses_persistent` is
+/// `false` when `DATABASE_URL` is unset, in which case pauses are lost on restart.
+pub async fn handle_projects_get(
+    Extension(config): Extension<AppConfig>,
+    Extension(admin): Extension<AdminState>,
+    Extension(secrets): Extension<WebhookSecrets>,
+    Extension(tracker): Extension<DeliveryTracker>,
+) -> Result<Response, Error> {
+    let watched: BTreeSet<String> = config
+        .webhooks()
+        .map(|webhooks| {
+            webhooks
+                .resources
+                .iter()
+                .map(|resource| resource.gid.clone())
+                .collect()
+        })
+        .unwrap_or_default();
+    let records = secrets.list().await?;
+
+    let mut gids = watched.clone();
+    gids.extend(records.iter().map(|record| record.resource_gid.clone()));
+
+    let mut projects = Vec::with_capacity(gids.len());
+    for gid in gids {
+        projects.push(ProjectStatus {
+            watched: watched.contains(&gid),
+            webhook_gid: records
+                .iter()
+                .find(|record| record.resource_gid == gid)
+                .and_then(|record| record.webhook_gid.clone()),
+            paused: admin.paused.is_paused(&gid).await,
+            seconds_since_last_delivery: tracker
+                .last_delivery(&gid)
+                .await
+                .map(|elapsed| elapsed.as_secs()),
+            gid,
+        });
+    }
+
+    Ok(return_json(
+        json!({ "data": projects, "pauses_persistent": admin.paused.is_persistent() }),
+        None,
+    )?
+    .into_response())
+}
+
+pub async fn handle_project_resync_post(
+    Path(gid): Path<String>,
+    Extension(handle): Extension<mpsc::Sender<TxMessage>>,
+) -> Result<Response, Error> {
+    handle
+        .send(TxMessage::Resync {
+            resource_gid: gid.clone(),
+        })
+        .await
+        .map_err(|err| Error::new(format!("Worker channel closed: {}", err)))?;
+    tracing::info!("Resync of `gid: {}` requested from the admin API", gid);
+
+    Ok(return_json(json!({ "status": "success" }), Some(StatusCode::ACCEPTED))?.into_response())
+}
+
+pub async fn handle_project_pause_post(
+    Path(gid): Path<String>,
+    Extension(admin): Extension<AdminState>,
+) -> Result<Response, Error> {
+    admin.paused.pause(&gid).await?;
+    tracing::info!("Replication of `gid: {}` paused", gid);
+
+    Ok(return_json(json!({ "status": "success" }), None)?.into_response())
+}
+
+/// Resume replication and resync, since events received while paused were skipped.
+pub async fn handle_project_resume_post(
+    Path(gid): Path<String>,
+    Extension(admin): Extension<AdminState>,
+    Extension(handle): Extension<mpsc::Sender<TxMessage>>,
+) -> Result<Response, Error> {
+    if admin.paused.resume(&gid).await? {
+        tracing::info!("Replication of `gid: {}` resumed", gid);
+        handle
+            .send(TxMessage::Resync { resource_gid: gid })
+            .await
+            .map_err(|err| Error::new(format!("Worker channel closed: {}", err)))?;
+    }
+
+    Ok(return_json(json!({ "status": "success" }), None)?.into_response())
+}
+
+pub async fn handle_webhooks_get(
+    Extension(config): Extension<AppConfig>,
+    Extension(admin): Extension<AdminState>,
+) -> Result<Response, Error> {
+    let (Some(client), Some(webhooks)) = (&admin.client, config.webhooks()) else {
+        return not_configured("webhook listing");
+    };
+    let data = webhooks::list_our_webhooks(client.as_ref(), &webhooks.workspace_gid).await?;
+
+    Ok(return_json(json!({ "data": data }), None)?.into_response())
+}
+
+/// Delete a webhook and forget its secret. A watched resource gets a new webhook on the next
+/// reconcile.
+pub async fn handle_webhook_delete(
+    Path(gid): Path<String>,
+    Extension(admin): Extension<AdminState>,
+    Extension(secrets): Extension<WebhookSecrets>,
+) -> Result<Response, Error> {
+    let Some(client) = &admin.client else {
+        return not_configured("Asana client");
+    };
+    // Keep the monitor from recreating the webhook before its secret is forgotten
+    let _changes = secrets.lock_changes().await;
+    asana::methods::delete_webhook(client.as_ref(), &gid).await?;
+
+    if let Some(record) = secrets
+        .list()
+        .await?
+        .into_iter()
+        .find(|record| record.webhook_gid.as_deref() == Some(gid.as_str()))
+    {
+        secrets.forget(&record.resource_gid).await?;
+    }
+    tracing::info!("Webhook `gid: {}` deleted from the admin API", gid);
+
+    Ok(return_json(json!({ "status": "success" }), None)?.into_response())
+}
+
+pub async fn handle_reconcile_post(
+    Extension(admin): Extension<AdminState>,
+) -> Result<Response, Error> {
+    let Some(reconciler) = &admin.reconciler else {
+        return not_configured("webhook reconciliation");
+    };
+    let report = reconciler.reconcile().await?;
+
+    Ok(return_json(json!(report), None)?.into_response())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EventsQuery {
+    resource_gid: Option<String>,
+    status: Option<DeliveryStatus>,
+    /// RFC 3339 timestamp.
+    since: Option<String>,
+    limit: Option<usize>,
+}
+
+/// Recently received deliveries with their events, newest first.
+pub async fn handle_events_get(
+    Query(query): Query<EventsQuery>,
+    Extension(queue): Extension<DeliveryQueue>,
+) -> Result<Response, Error> {
+    let since = match query.since.as_deref().map(str::parse::<DateTime>) {
+        Some(Ok(since)) => Some(bson::DateTime::from_millis(since.timestamp_millis())),
+        Some(Err(err)) => return bad_request(err.to_string()),
+        None => None,
+    };
+    let filter = DeliveryFilter {
+        resource_gid: query.resource_gid,
+        status: query.status,
+        since,
+    };
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_EVENTS_LIMIT)
+        .min(MAX_EVENTS_LIMIT);
+
+    let data: Vec<_> = queue
+        .recent(&filter, limit)
+        .await?
+        .into_iter()
+        .map(|delivery| {
+            let body: serde_json::Value =
+                serde_json::from_str(&delivery.body).unwrap_or(serde_json::Value::Null);
+            json!({
+                "id": delivery.id,
+                "resource_gid": delivery.resource_gid,
+                "received_at": delivery.received_at.try_to_rfc3339_string().ok(),
+                "status": delivery.status,
+                "events": body["events"],
+            })
+        })
+        .collect();
+
+    Ok(return_json(json!({ "data": data }), None)?.into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::webhooks::deliveries::InMemoryDeliveryStore;
+    use axum::body::Body;
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    const TOKEN: &str = "s3cret";
+
+    /// `admin_router` with the extensions the server provides, and a worker channel that fits
+    /// one event so a second delivery is spilled. The channel stays open while the receiver is
+    /// held.
+    async fn router(
+        admin_token: Option<&str>,
+    ) -> (Router, DeliveryQueue, mpsc::Receiver<TxMessage>) {
+        let (sender, receiver) = mpsc::channel(1);
+        let queue = DeliveryQueue::new(sender.clone(), InMemoryDeliveryStore::default())
+            .await
+            .unwrap();
+        let router = admin_router()
+            .layer(Extension(AppConfig::with_admin_token(admin_token)))
+            .layer(Extension(sender))
+            .layer(Extension(queue.clone()))
+            .layer(Extension(WebhookSecrets::in_memory()))
+            .layer(Extension(DeliveryTracker::default()))
+            .layer(Extension(AdminState::default()));
+
+        (router, queue, receiver)
+    }
+
+    async fn get(router: Router, uri: &str, authorization: Option<&str>) -> (StatusCode, Value) {
+        let mut request = Request::get(uri);
+        if let Some(authorization) = authorization {
+            request = request.header(header::AUTHORIZATION, authorization);
+        }
+        let response = router
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    async fn events(router: &Router, query: &str) -> Vec<Value> {
+        let (status, body) = get(
+            router.clone(),
+            &format!("/events{}", query),
+            Some("Bearer s3cret"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        body["data"].as_array().unwrap().clone()
+    }
+
+    fn event(gid: &str) -> asana::types::WebhookEvent {
+        serde_json::from_value(json!({
+            "action": "changed",
+            "resource": { "gid": gid, "resource_type": "task" },
+            "created_at": "2024-01-01T00:00:00.000Z",
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_token() {
+        let (router, _, _receiver) = router(Some(TOKEN)).await;
+
+        let (status, _) = get(router, "/events", None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn rejects_wrong_token() {
+        let (router, _, _receiver) = router(Some(TOKEN)).await;
+
+        let (status, _) = get(router, "/events", Some("Bearer s3cre")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn rejects_other_schemes() {
+        let (router, _, _receiver) = router(Some(TOKEN)).await;
+
+        let (status, _) = get(router.clone(), "/events", Some(TOKEN)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = get(router, "/events", Some("Basic s3cret")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn rejects_everything_without_configured_token() {
+        let (router, _, _receiver) = router(None).await;
+
+        let (status, _) = get(router.clone(), "/events", None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = get(router, "/events", Some("Bearer ")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn accepts_configured_token() {
+        let (router, _, _receiver) = router(Some(TOKEN)).await;
+
+        let (status, body) = get(router, "/projects", Some("Bearer s3cret")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["pauses_persistent"], false);
+    }
+
+    #[tokio::test]
+    async fn filters_events() {
+        let (router, queue, _receiver) = router(Some(TOKEN)).await;
+        queue
+            .enqueue("1", String::from("{}"), vec![event("10")])
+            .await
+            .unwrap();
+        queue
+            .enqueue("2", String::from("{}"), vec![event("20")])
+            .await
+            .unwrap();
+
+        assert_eq!(events(&router, "").await.len(), 2);
+        assert_eq!(events(&router, "?limit=1").await.len(), 1);
+
+        let spilled = events(&router, "?status=spilled").await;
+        assert_eq!(spilled.len(), 1);
+        assert_eq!(spilled[0]["resource_gid"], "2");
+
+        let first = events(&router, "?resource_gid=1").await;
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0]["status"], "queued");
+
+        assert!(events(&router, "?since=2999-01-01T00:00:00Z")
+            .await
+            .is_empty());
+        assert_eq!(
+            events(&router, "?since=2000-01-01T00:00:00Z").await.len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_malformed_since() {
+        let (router, _, _receiver) = router(Some(TOKEN)).await;
+
+        let (status, _) = get(router, "/events?since=yesterday", Some("Bearer s3cret")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -12,7 +12,9 @@ use hyper_rustls::HttpsConnector;
 use serde_json::Value;
 use std::{error::Error as StdError, fmt, ops, thread, time::Duration};
 
+pub mod dates;
 pub mod methods;
+pub mod rich_text;
 pub mod types;
 
 /// GET method
@@ -26,6 +28,9 @@ pub const DELETE: Method = Method::DELETE;
 
 const RETRY_ATTEMPTS: u32 = 5;
 
+/// Page size requested from paginated list endpoints (Asana's maximum).
+pub const PAGE_LIMIT: &str = "100";
+
 /// Represents a (Hyper) HTTP client.
 #[derive(Debug)]
 pub struct Client {
@@ -95,7 +100,7 @@ impl HTTPClient for Client {
                             Ok(value) => Ok(value),
                             Err(err) => Err(Error::new(format!(
                                 "Unable to deserialize bytes `{:?}` into `{}` / Error: {}",
-                                &bytes, caller_type, err
+                                bytes, caller_type, err
                             ))),
                         };
 
@@ -113,12 +118,11 @@ impl HTTPClient for Client {
                             _ => {
                                 let text = format!(
                                     "Request Failure: Status: {} / Bytes: {}",
-                                    status,
-                                    bytes.to_string()
+                                    status, bytes
                                 );
                                 tracing::debug!(text);
 
-                                return Err(Error::new(text));
+                                return Err(Error::with_status(status, text));
                             }
                         };
 
@@ -191,7 +195,7 @@ impl ops::Deref for RetryErrors<'_> {
 }
 
 /// Attempt exponential backoff when re-attempting requests.
-async fn retry_with_backoff<'a>(
+async fn retry_with_backoff(
     client: &Client,
     method: &hyper::Method,
     asana_personal_access_token: &str,
@@ -253,16 +257,34 @@ async fn retry_with_backoff<'a>(
     // Err(Error::new_internal_error())
 }
 
+/// Build the query string for every request. Values are fully percent-encoded (see
+/// [`percent_encode`]), not just `+`, so commas, spaces and `&` in e.g. search text or
+/// `opt_fields` reach Asana intact.
 fn url_encode(params: &[(&str, &str)]) -> String {
     params
         .iter()
         .map(|&t| {
             let (k, v) = t;
-            format!("{}={}", k, v)
+            format!("{}={}", k, percent_encode(v))
         })
         .fold("".to_string(), |mut acc, item| {
             acc.push_str(&item);
             acc.push('&');
-            acc.replace('+', "%2B")
+            acc
+        })
+}
+
+/// Percent-encode a query value, leaving only RFC 3986 unreserved characters intact.
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .fold(String::with_capacity(value.len()), |mut acc, byte| {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                    acc.push(byte as char)
+                }
+                _ => acc.push_str(&format!("%{:02X}", byte)),
+            }
+            acc
         })
 }
@@ -0,0 +1,285 @@
+//! Asana dates and timestamps
+//!
+//! Asana sends calendar dates (`due_on`, `start_on`) as `YYYY-MM-DD` with no timezone, meaning
+//! the day in the workspace's local time, and instants (`created_at`, `due_at`) as UTC ISO 8601
+//! with millisecond precision. These wrappers parse and serialize exactly those formats.
+
+use crate::error::Error;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, TimeZone, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{fmt, ops, str::FromStr};
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const DATE_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.3fZ";
+
+/// A calendar date such as `due_on`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date(pub NaiveDate);
+
+/// A UTC instant such as `created_at`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime(pub chrono::DateTime<Utc>);
+
+impl Date {
+    /// Today's date as seen in `tz`, e.g. the workspace's timezone.
+    pub fn today_in<Tz: TimeZone>(tz: &Tz) -> Self {
+        Self(Utc::now().with_timezone(tz).date_naive())
+    }
+
+    /// The instant this day starts in `tz`. If local midnight is skipped by a DST transition the
+    /// day starts at the first valid local time after the gap; if it occurs twice, the earlier.
+    pub fn start_in<Tz: TimeZone>(&self, tz: &Tz) -> DateTime {
+        let midnight = self.0.and_time(NaiveTime::MIN);
+
+        match tz.from_local_datetime(&midnight).earliest() {
+            Some(local) => DateTime(local.with_timezone(&Utc)),
+            None => DateTime(first_instant_from(tz, &midnight)),
+        }
+    }
+
+    /// The instant the following day starts in `tz`; a task due on this date is overdue from
+    /// then on.
+    pub fn end_in<Tz: TimeZone>(&self, tz: &Tz) -> DateTime {
+        match self.0.succ_opt() {
+            Some(next) => Date(next).start_in(tz),
+            None => DateTime(chrono::DateTime::<Utc>::MAX_UTC),
+        }
+    }
+
+    /// Whether a task due on this date is overdue at `now`, for a workspace in `tz`.
+    pub fn is_overdue_at<Tz: TimeZone>(&self, now: &DateTime, tz: &Tz) -> bool {
+        *now >= self.end_in(tz)
+    }
+}
+
+/// The first instant whose local time in `tz` is at or after `local`, found by bisecting to the
+/// second. UTC offsets are under a day, so it lies within a day either side of `local` read as UTC.
+fn first_instant_from<Tz: TimeZone>(tz: &Tz, local: &NaiveDateTime) -> chrono::DateTime<Utc> {
+    let mut before = local.and_utc() - TimeDelta::days(1);
+    let mut after = local.and_utc() + TimeDelta::days(1);
+
+    while after - before > TimeDelta::seconds(1) {
+        let mid = before + TimeDelta::seconds((after - before).num_seconds() / 2);
+        if mid.with_timezone(tz).naive_local() >= *local {
+            after = mid;
+        } else {
+            before = mid;
+        }
+    }
+
+    after
+}
+
+impl DateTime {
+    pub fn now() -> Self {
+        Self(Utc::now())
+    }
+
+    /// The calendar date of this instant as seen in `tz`.
+    pub fn date_in<Tz: TimeZone>(&self, tz: &Tz) -> Date {
+        Date(self.0.with_timezone(tz).date_naive())
+    }
+}
+
+impl ops::Deref for Date {
+    type Target = NaiveDate;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl ops::Deref for DateTime {
+    type Target = chrono::DateTime<Utc>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<NaiveDate> for Date {
+    fn from(value: NaiveDate) -> Self {
+        Self(value)
+    }
+}
+
+impl From<chrono::DateTime<Utc>> for DateTime {
+    fn from(value: chrono::DateTime<Utc>) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.format(DATE_FORMAT))
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.format(DATE_TIME_FORMAT))
+    }
+}
+
+impl FromStr for Date {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        NaiveDate::parse_from_str(s, DATE_FORMAT)
+            .map(Self)
+            .map_err(|err| Error::new(format!("Invalid Asana date `{}`: {}", s, err)))
+    }
+}
+
+impl FromStr for DateTime {
+    type Err = Error;
+
+    /// Accepts any RFC 3339 timestamp, normalising it to UTC.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        chrono::DateTime::parse_from_rfc3339(s)
+            .map(|date_time| Self(date_time.with_timezone(&Utc)))
+            .map_err(|err| Error::new(format!("Invalid Asana timestamp `{}`: {}", s, err)))
+    }
+}
+
+impl Serialize for Date {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl Serialize for DateTime {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Date {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for DateTime {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, MappedLocalTime};
+
+    /// A zone with a single transition from `before` to `after` at the UTC instant `at`.
+    #[derive(Debug, Clone)]
+    struct Transition {
+        at: NaiveDateTime,
+        before: FixedOffset,
+        after: FixedOffset,
+    }
+
+    impl Transition {
+        fn new(at: &str, before_hours: i32, after_hours: i32) -> Self {
+            Self {
+                at: at.parse::<DateTime>().unwrap().0.naive_utc(),
+                before: FixedOffset::east_opt(before_hours * 3600).unwrap(),
+                after: FixedOffset::east_opt(after_hours * 3600).unwrap(),
+            }
+        }
+    }
+
+    impl TimeZone for Transition {
+        type Offset = FixedOffset;
+
+        fn from_offset(offset: &FixedOffset) -> Self {
+            Self {
+                at: NaiveDateTime::MIN,
+                before: *offset,
+                after: *offset,
+            }
+        }
+
+        fn offset_from_local_date(&self, local: &NaiveDate) -> MappedLocalTime<FixedOffset> {
+            self.offset_from_local_datetime(&local.and_time(NaiveTime::MIN))
+        }
+
+        fn offset_from_local_datetime(
+            &self,
+            local: &NaiveDateTime,
+        ) -> MappedLocalTime<FixedOffset> {
+            let before = *local - self.before < self.at;
+            let after = *local - self.after >= self.at;
+            match (before, after) {
+                (true, true) => MappedLocalTime::Ambiguous(self.before, self.after),
+                (true, false) => MappedLocalTime::Single(self.before),
+                (false, true) => MappedLocalTime::Single(self.after),
+                (false, false) => MappedLocalTime::None,
+            }
+        }
+
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+            self.offset_from_utc_datetime(&utc.and_time(NaiveTime::MIN))
+        }
+
+        fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+            if *utc < self.at {
+                self.before
+            } else {
+                self.after
+            }
+        }
+    }
+
+    fn date(date: &str) -> Date {
+        date.parse().unwrap()
+    }
+
+    fn instant(instant: &str) -> DateTime {
+        instant.parse().unwrap()
+    }
+
+    #[test]
+    fn start_in_fixed_offset() {
+        let tz = FixedOffset::east_opt(2 * 3600).unwrap();
+        assert_eq!(
+            date("2024-03-10").start_in(&tz),
+            instant("2024-03-09T22:00:00.000Z")
+        );
+    }
+
+    #[test]
+    fn start_in_gap_at_midnight() {
+        // Clocks go from 00:00 at -04:00 straight to 01:00 at -03:00
+        let tz = Transition::new("2024-09-08T04:00:00.000Z", -4, -3);
+        let start = date("2024-09-08").start_in(&tz);
+        assert_eq!(start, instant("2024-09-08T04:00:00.000Z"));
+        assert_eq!(start.date_in(&tz), date("2024-09-08"));
+    }
+
+    #[test]
+    fn start_in_gap_spanning_midnight() {
+        // Clocks go from 23:30 at +00:00 straight to 00:30 at +01:00
+        let tz = Transition::new("2024-03-30T23:30:00.000Z", 0, 1);
+        assert_eq!(
+            date("2024-03-31").start_in(&tz),
+            instant("2024-03-30T23:30:00.000Z")
+        );
+        assert_eq!(
+            date("2024-03-30").end_in(&tz),
+            instant("2024-03-30T23:30:00.000Z")
+        );
+    }
+
+    #[test]
+    fn start_in_overlap_takes_earlier_midnight() {
+        // Clocks go back from 00:30 at -03:00 to 23:30 the previous day at -04:00
+        let tz = Transition::new("2024-04-07T03:30:00.000Z", -3, -4);
+        assert_eq!(
+            date("2024-04-07").start_in(&tz),
+            instant("2024-04-07T03:00:00.000Z")
+        );
+    }
+}
@@ -4,20 +4,46 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::env;
 
-#[derive(Debug, Deserialize, Serialize)]
-pub struct AsanaResponse {
-    pub data: Vec<std::collections::HashMap<String, String>>,
-}
+mod audit_log;
+mod goals;
+mod jobs;
+mod portfolios;
+mod projects;
+mod search;
+mod status_updates;
+mod tags;
+mod tasks;
+mod templates;
+mod time_tracking;
+mod typeahead;
+mod users;
+
+pub use audit_log::*;
+pub use goals::*;
+pub use jobs::*;
+pub use portfolios::*;
+pub use projects::*;
+pub use search::*;
+pub use status_updates::*;
+pub use tags::*;
+pub use tasks::*;
+pub use templates::*;
+pub use time_tracking::*;
+pub use typeahead::*;
+pub use users::*;
 
 pub async fn get_workspaces(
     client: &impl asana::HTTPClient,
-) -> Result<(AsanaResponse, serde_json::Value), crate::error::Error> {
-    let params = vec![("", "")];
-    let path = format!("workspaces");
+) -> Result<(types::ListResponse<types::Workspace>, serde_json::Value), crate::error::Error> {
+    let params = vec![
+        ("limit", asana::PAGE_LIMIT),
+        ("opt_fields", "name,is_organization,email_domains"),
+    ];
+    let path = String::from("workspaces");
 
     let body = None;
     let result = match client
-        .send_request::<AsanaResponse>("GET", &path, &params, body)
+        .send_request::<types::ListResponse<types::Workspace>>("GET", &path, &params, body)
         .await
     {
         Ok(value) => value,
@@ -41,20 +67,18 @@ pub struct CreateTeamResponseData {
     pub gid: String,
     pub resource_type: String,
     pub name: String,
-    #[serde(skip)]
-    pub description: String,
-    #[serde(skip)]
-    pub html_description: String,
+    pub description: Option<String>,
+    pub html_description: Option<String>,
     pub organization: TeamOrganization,
     pub permalink_url: String,
-    pub visibility: String,
-    pub edit_team_name_or_description_access_level: String,
-    pub edit_team_visibility_or_trash_team_access_level: String,
-    pub member_invite_management_access_level: String,
-    pub guest_invite_management_access_level: String,
-    pub join_request_management_access_level: String,
-    pub team_member_removal_access_level: String,
-    pub team_content_management_access_level: String,
+    pub visibility: types::Visibility,
+    pub edit_team_name_or_description_access_level: types::TeamAccessLevel,
+    pub edit_team_visibility_or_trash_team_access_level: types::TeamAccessLevel,
+    pub member_invite_management_access_level: types::TeamAccessLevel,
+    pub guest_invite_management_access_level: types::TeamAccessLevel,
+    pub join_request_management_access_level: types::TeamAccessLevel,
+    pub team_member_removal_access_level: types::TeamAccessLevel,
+    pub team_content_management_access_level: types::TeamAccessLevel,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -70,7 +94,7 @@ pub async fn create_team(
     name: &str,
 ) -> Result<(CreateTeamResponse, serde_json::Value), crate::error::Error> {
     let params = vec![("", "")];
-    let path = format!("teams");
+    let path = String::from("teams");
 
     let body = json!({
             "data": {
@@ -109,7 +133,7 @@ pub async fn create_project(
     name: &str,
 ) -> Result<(CreateProjectResponse, serde_json::Value), crate::error::Error> {
     let params: Vec<(&str, &str)> = Vec::new();
-    let path = format!("projects");
+    let path = String::from("projects");
 
     let body = json!({
         "data": {
@@ -138,22 +162,43 @@ pub async fn create_project(
     Ok(result)
 }
 
-pub async fn create_webhook(
-    client: &impl asana::HTTPClient,
-    project_gid: &str,
-) -> Result<(types::Webhook, serde_json::Value), crate::error::Error> {
+#[derive(Debug, Deserialize, Serialize)]
+pub struct WebhookResponse {
+    pub data: types::WebhookData,
+}
+
+/// URL Asana delivers events for `resource_gid` to; see `server::handle_receive_webhook`.
+pub fn webhook_target(resource_gid: &str) -> String {
     let webhook_prefix: &str = &env::var("ASANA_WEBHOOK_TLS_URI")
         .unwrap_or("https://REPLACEME.ngrok-free.app".to_string());
-    let webhook_uri = format!("{}/receive-webhook/{}", &webhook_prefix, &project_gid);
-    let params = vec![("resource", project_gid), ("target", webhook_uri.as_str())];
-    let path = format!("webhooks");
 
-    let body = json!({}).to_string();
+    format!("{}/receive-webhook/{}", webhook_prefix, resource_gid)
+}
+
+/// Create a webhook on a project (or other resource) delivering to [`webhook_target`].
+///
+/// Asana does not return the handshake secret here; it is only sent as `X-Hook-Secret` to the
+/// target while this request is in flight.
+pub async fn create_webhook(
+    client: &impl asana::HTTPClient,
+    resource_gid: &str,
+    filters: &[types::Filter],
+) -> Result<(WebhookResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = String::from("webhooks");
+
+    let mut data = json!({
+        "resource": resource_gid,
+        "target": webhook_target(resource_gid),
+    });
+    if !filters.is_empty() {
+        data["filters"] = json!(filters);
+    }
+    let body = json!({ "data": data }).to_string();
     let body = Some(body);
 
-    dbg!(&params);
     let result = match client
-        .send_request::<types::Webhook>("POST", &path, &params, body)
+        .send_request::<WebhookResponse>("POST", &path, &params, body)
         .await
     {
         Ok(value) => value,
@@ -167,3 +212,136 @@ pub async fn create_webhook(
 
     Ok(result)
 }
+
+/// Create a webhook on a whole workspace. Asana rejects workspace-level webhooks without filters.
+pub async fn create_workspace_webhook(
+    client: &impl asana::HTTPClient,
+    workspace_gid: &str,
+    filters: &[types::Filter],
+) -> Result<(WebhookResponse, serde_json::Value), crate::error::Error> {
+    if filters.is_empty() {
+        return Err(crate::error::Error::new(format!(
+            "Workspace webhook for `gid: {}` requires at least one filter",
+            workspace_gid
+        )));
+    }
+
+    create_webhook(client, workspace_gid, filters).await
+}
+
+/// List webhooks in a workspace, optionally only those on `resource_gid`.
+/// Fields requested when listing webhooks; list responses are compact records otherwise.
+const WEBHOOK_OPT_FIELDS: &str = "active,resource,resource.name,target,filters,created_at,last_failure_at,last_failure_content,last_success_at";
+
+pub async fn get_webhooks(
+    client: &impl asana::HTTPClient,
+    workspace_gid: &str,
+    resource_gid: Option<&str>,
+    offset: Option<&str>,
+) -> Result<(types::ListResponse<types::WebhookData>, serde_json::Value), crate::error::Error> {
+    let mut params = vec![
+        ("workspace", workspace_gid),
+        ("limit", asana::PAGE_LIMIT),
+        ("opt_fields", WEBHOOK_OPT_FIELDS),
+    ];
+    if let Some(resource_gid) = resource_gid {
+        params.push(("resource", resource_gid));
+    }
+    if let Some(offset) = offset {
+        params.push(("offset", offset));
+    }
+    let path = String::from("webhooks");
+
+    let body = None;
+    let result = match client
+        .send_request::<types::ListResponse<types::WebhookData>>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn get_webhook(
+    client: &impl asana::HTTPClient,
+    webhook_gid: &str,
+) -> Result<(WebhookResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("webhooks/{}", webhook_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<WebhookResponse>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        // Keep the status so callers can tell a deleted webhook from a failed request
+        Err(err) => return Err(err.context("GET request failed")),
+    };
+
+    Ok(result)
+}
+
+/// Replace the filters of an existing webhook.
+pub async fn update_webhook(
+    client: &impl asana::HTTPClient,
+    webhook_gid: &str,
+    filters: &[types::Filter],
+) -> Result<(WebhookResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("webhooks/{}", webhook_gid);
+
+    let body = json!({
+        "data": {
+            "filters": filters,
+        }
+    })
+    .to_string();
+    let body = Some(body);
+
+    let result = match client
+        .send_request::<WebhookResponse>("PUT", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "PUT request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn delete_webhook(
+    client: &impl asana::HTTPClient,
+    webhook_gid: &str,
+) -> Result<(types::EmptyResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("webhooks/{}", webhook_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<types::EmptyResponse>("DELETE", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "DELETE request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
@@ -0,0 +1,93 @@
+use crate::asana::dates::DateTime;
+use crate::asana::types;
+use crate::asana::{self};
+use futures::stream::{self, Stream, TryStreamExt};
+
+/// Filters for [`get_audit_log_events`]; unset fields are not sent.
+#[derive(Debug, Default, Clone)]
+pub struct AuditLogQuery {
+    /// Only events created at or after this instant.
+    pub start_at: Option<DateTime>,
+    /// Only events created before this instant.
+    pub end_at: Option<DateTime>,
+    pub event_type: Option<String>,
+    pub actor_type: Option<String>,
+    pub actor_gid: Option<String>,
+    pub resource_gid: Option<String>,
+}
+
+/// Fetch one page of audit log events for an enterprise workspace.
+pub async fn get_audit_log_events(
+    client: &impl asana::HTTPClient,
+    workspace_gid: &str,
+    query: &AuditLogQuery,
+    offset: Option<&str>,
+) -> Result<(types::ListResponse<types::AuditLogEvent>, serde_json::Value), crate::error::Error> {
+    let start_at = query.start_at.map(|start_at| start_at.to_string());
+    let end_at = query.end_at.map(|end_at| end_at.to_string());
+    let mut params = vec![("limit", asana::PAGE_LIMIT)];
+    for (key, value) in [
+        ("start_at", &start_at),
+        ("end_at", &end_at),
+        ("event_type", &query.event_type),
+        ("actor_type", &query.actor_type),
+        ("actor_gid", &query.actor_gid),
+        ("resource_gid", &query.resource_gid),
+    ] {
+        if let Some(value) = value {
+            params.push((key, value.as_str()));
+        }
+    }
+    if let Some(offset) = offset {
+        params.push(("offset", offset));
+    }
+    let path = format!("workspaces/{}/audit_log_events", workspace_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<types::ListResponse<types::AuditLogEvent>>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+/// Stream every audit log event matching `query`, fetching pages lazily.
+///
+/// The audit log always returns a `next_page`, so the stream ends on the first empty page.
+pub fn stream_audit_log_events<'a>(
+    client: &'a impl asana::HTTPClient,
+    workspace_gid: &'a str,
+    query: &'a AuditLogQuery,
+) -> impl Stream<Item = Result<types::AuditLogEvent, crate::error::Error>> + 'a {
+    stream::try_unfold(
+        Some(None),
+        move |state: Option<Option<String>>| async move {
+            let offset = match state {
+                Some(offset) => offset,
+                None => return Ok::<_, crate::error::Error>(None),
+            };
+
+            let (page, _) =
+                get_audit_log_events(client, workspace_gid, query, offset.as_deref()).await?;
+            if page.data.is_empty() {
+                return Ok(None);
+            }
+            let next_state = page.next_page.map(|next_page| Some(next_page.offset));
+
+            Ok(Some((
+                stream::iter(page.data.into_iter().map(Ok)),
+                next_state,
+            )))
+        },
+    )
+    .try_flatten()
+}
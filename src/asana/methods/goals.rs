@@ -0,0 +1,263 @@
+use crate::asana::dates::Date;
+use crate::asana::types;
+use crate::asana::{self};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GoalResponse {
+    pub data: types::Goal,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GoalRelationshipResponse {
+    pub data: types::GoalRelationship,
+}
+
+/// The resource goals are listed for; Asana requires exactly one of these.
+#[derive(Debug, Clone, Copy)]
+pub enum GoalParent<'a> {
+    Workspace(&'a str),
+    Team(&'a str),
+    Project(&'a str),
+    Portfolio(&'a str),
+}
+
+impl<'a> GoalParent<'a> {
+    fn param(&self) -> (&'static str, &'a str) {
+        match *self {
+            GoalParent::Workspace(gid) => ("workspace", gid),
+            GoalParent::Team(gid) => ("team", gid),
+            GoalParent::Project(gid) => ("project", gid),
+            GoalParent::Portfolio(gid) => ("portfolio", gid),
+        }
+    }
+}
+
+/// Fields that may be changed on a goal; `None` leaves the field untouched.
+#[derive(Debug, Default, Serialize)]
+pub struct UpdateGoal {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_on: Option<Date>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_on: Option<Date>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+}
+
+pub async fn get_goals(
+    client: &impl asana::HTTPClient,
+    parent: GoalParent<'_>,
+    offset: Option<&str>,
+) -> Result<(types::ListResponse<types::Goal>, serde_json::Value), crate::error::Error> {
+    let mut params = vec![parent.param(), ("limit", asana::PAGE_LIMIT)];
+    if let Some(offset) = offset {
+        params.push(("offset", offset));
+    }
+    let path = String::from("goals");
+
+    let body = None;
+    let result = match client
+        .send_request::<types::ListResponse<types::Goal>>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn get_goal(
+    client: &impl asana::HTTPClient,
+    goal_gid: &str,
+) -> Result<(GoalResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("goals/{}", goal_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<GoalResponse>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn update_goal(
+    client: &impl asana::HTTPClient,
+    goal_gid: &str,
+    update: &UpdateGoal,
+) -> Result<(GoalResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("goals/{}", goal_gid);
+
+    let body = json!({ "data": update }).to_string();
+    let body = Some(body);
+
+    let result = match client
+        .send_request::<GoalResponse>("PUT", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "PUT request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+/// Record progress against a goal's metric.
+pub async fn set_goal_metric_current_value(
+    client: &impl asana::HTTPClient,
+    goal_gid: &str,
+    current_number_value: f64,
+) -> Result<(GoalResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("goals/{}/setMetricCurrentValue", goal_gid);
+
+    let body = json!({
+        "data": {
+            "current_number_value": current_number_value,
+        }
+    })
+    .to_string();
+    let body = Some(body);
+
+    let result = match client
+        .send_request::<GoalResponse>("POST", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "POST request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn get_goal_relationships(
+    client: &impl asana::HTTPClient,
+    goal_gid: &str,
+    offset: Option<&str>,
+) -> Result<
+    (
+        types::ListResponse<types::GoalRelationship>,
+        serde_json::Value,
+    ),
+    crate::error::Error,
+> {
+    let mut params = vec![("supported_goal", goal_gid), ("limit", asana::PAGE_LIMIT)];
+    if let Some(offset) = offset {
+        params.push(("offset", offset));
+    }
+    let path = String::from("goal_relationships");
+
+    let body = None;
+    let result = match client
+        .send_request::<types::ListResponse<types::GoalRelationship>>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+/// Link a supporting goal, project or portfolio to a goal.
+pub async fn add_supporting_relationship(
+    client: &impl asana::HTTPClient,
+    goal_gid: &str,
+    supporting_resource_gid: &str,
+    contribution_weight: Option<f64>,
+) -> Result<(GoalRelationshipResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("goals/{}/addSupportingRelationship", goal_gid);
+
+    let mut data = json!({ "supporting_resource": supporting_resource_gid });
+    if let Some(contribution_weight) = contribution_weight {
+        data["contribution_weight"] = json!(contribution_weight);
+    }
+    let body = json!({ "data": data }).to_string();
+    let body = Some(body);
+
+    let result = match client
+        .send_request::<GoalRelationshipResponse>("POST", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "POST request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn remove_supporting_relationship(
+    client: &impl asana::HTTPClient,
+    goal_gid: &str,
+    supporting_resource_gid: &str,
+) -> Result<(types::EmptyResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("goals/{}/removeSupportingRelationship", goal_gid);
+
+    let body = json!({
+        "data": {
+            "supporting_resource": supporting_resource_gid,
+        }
+    })
+    .to_string();
+    let body = Some(body);
+
+    let result = match client
+        .send_request::<types::EmptyResponse>("POST", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "POST request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
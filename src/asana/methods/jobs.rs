@@ -0,0 +1,78 @@
+use crate::asana::types;
+use crate::asana::{self};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Delay between successive polls of a pending job.
+const JOB_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct JobResponse {
+    pub data: types::Job,
+}
+
+pub async fn get_job(
+    client: &impl asana::HTTPClient,
+    job_gid: &str,
+) -> Result<(JobResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("jobs/{}", job_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<JobResponse>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+/// Poll an asynchronous job until it succeeds, fails or `timeout` elapses.
+pub async fn wait_for_job(
+    client: &impl asana::HTTPClient,
+    job_gid: &str,
+    timeout: Duration,
+) -> Result<types::Job, crate::error::Error> {
+    let started = tokio::time::Instant::now();
+
+    loop {
+        let (resp, _) = get_job(client, job_gid).await?;
+        let job = resp.data;
+        tracing::info!(
+            "Job `gid: {}` status: {:?} ({:?} elapsed)",
+            job.gid,
+            job.status,
+            started.elapsed()
+        );
+
+        match job.status {
+            types::JobStatus::Succeeded => return Ok(job),
+            types::JobStatus::Failed => {
+                return Err(crate::error::Error::new(format!(
+                    "Job `gid: {}` failed",
+                    job.gid
+                )))
+            }
+            types::JobStatus::NotStarted
+            | types::JobStatus::InProgress
+            | types::JobStatus::Unknown(_) => {}
+        }
+
+        if started.elapsed() + JOB_POLL_INTERVAL > timeout {
+            return Err(crate::error::Error::new(format!(
+                "Timed out after {:?} waiting for job `gid: {}`",
+                timeout, job.gid
+            )));
+        }
+
+        tokio::time::sleep(JOB_POLL_INTERVAL).await;
+    }
+}
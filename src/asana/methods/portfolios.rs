@@ -0,0 +1,272 @@
+use crate::asana::types;
+use crate::asana::{self};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PortfolioResponse {
+    pub data: types::Portfolio,
+}
+
+/// List the portfolios in a workspace owned by `owner` (a user gid, email or `me`).
+pub async fn get_portfolios(
+    client: &impl asana::HTTPClient,
+    workspace_gid: &str,
+    owner: &str,
+    offset: Option<&str>,
+) -> Result<(types::ListResponse<types::Portfolio>, serde_json::Value), crate::error::Error> {
+    let mut params = vec![
+        ("workspace", workspace_gid),
+        ("owner", owner),
+        ("limit", asana::PAGE_LIMIT),
+    ];
+    if let Some(offset) = offset {
+        params.push(("offset", offset));
+    }
+    let path = String::from("portfolios");
+
+    let body = None;
+    let result = match client
+        .send_request::<types::ListResponse<types::Portfolio>>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn create_portfolio(
+    client: &impl asana::HTTPClient,
+    workspace_gid: &str,
+    name: &str,
+    color: Option<types::Color>,
+    public: bool,
+) -> Result<(PortfolioResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = String::from("portfolios");
+
+    let mut data = json!({
+        "name": name,
+        "workspace": workspace_gid,
+        "public": public,
+    });
+    if let Some(color) = color {
+        data["color"] = json!(color.as_str());
+    }
+    let body = json!({ "data": data }).to_string();
+    let body = Some(body);
+
+    let result = match client
+        .send_request::<PortfolioResponse>("POST", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "POST request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn get_portfolio_items(
+    client: &impl asana::HTTPClient,
+    portfolio_gid: &str,
+    offset: Option<&str>,
+) -> Result<(types::ListResponse<types::Project>, serde_json::Value), crate::error::Error> {
+    let mut params = vec![("limit", asana::PAGE_LIMIT)];
+    if let Some(offset) = offset {
+        params.push(("offset", offset));
+    }
+    let path = format!("portfolios/{}/items", portfolio_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<types::ListResponse<types::Project>>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn add_portfolio_item(
+    client: &impl asana::HTTPClient,
+    portfolio_gid: &str,
+    item_gid: &str,
+) -> Result<(types::EmptyResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("portfolios/{}/addItem", portfolio_gid);
+
+    let body = json!({
+        "data": {
+            "item": item_gid,
+        }
+    })
+    .to_string();
+    let body = Some(body);
+
+    let result = match client
+        .send_request::<types::EmptyResponse>("POST", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "POST request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn remove_portfolio_item(
+    client: &impl asana::HTTPClient,
+    portfolio_gid: &str,
+    item_gid: &str,
+) -> Result<(types::EmptyResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("portfolios/{}/removeItem", portfolio_gid);
+
+    let body = json!({
+        "data": {
+            "item": item_gid,
+        }
+    })
+    .to_string();
+    let body = Some(body);
+
+    let result = match client
+        .send_request::<types::EmptyResponse>("POST", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "POST request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn get_portfolio_custom_field_settings(
+    client: &impl asana::HTTPClient,
+    portfolio_gid: &str,
+    offset: Option<&str>,
+) -> Result<
+    (
+        types::ListResponse<types::CustomFieldSetting>,
+        serde_json::Value,
+    ),
+    crate::error::Error,
+> {
+    let mut params = vec![("limit", asana::PAGE_LIMIT)];
+    if let Some(offset) = offset {
+        params.push(("offset", offset));
+    }
+    let path = format!("portfolios/{}/custom_field_settings", portfolio_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<types::ListResponse<types::CustomFieldSetting>>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn add_portfolio_custom_field_setting(
+    client: &impl asana::HTTPClient,
+    portfolio_gid: &str,
+    custom_field_gid: &str,
+    is_important: bool,
+) -> Result<(types::EmptyResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("portfolios/{}/addCustomFieldSetting", portfolio_gid);
+
+    let body = json!({
+        "data": {
+            "custom_field": custom_field_gid,
+            "is_important": is_important,
+        }
+    })
+    .to_string();
+    let body = Some(body);
+
+    let result = match client
+        .send_request::<types::EmptyResponse>("POST", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "POST request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn remove_portfolio_custom_field_setting(
+    client: &impl asana::HTTPClient,
+    portfolio_gid: &str,
+    custom_field_gid: &str,
+) -> Result<(types::EmptyResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("portfolios/{}/removeCustomFieldSetting", portfolio_gid);
+
+    let body = json!({
+        "data": {
+            "custom_field": custom_field_gid,
+        }
+    })
+    .to_string();
+    let body = Some(body);
+
+    let result = match client
+        .send_request::<types::EmptyResponse>("POST", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "POST request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
@@ -0,0 +1,173 @@
+use crate::asana::dates::Date;
+use crate::asana::types;
+use crate::asana::{self};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::Duration;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ProjectResponse {
+    pub data: types::ProjectResponseData,
+}
+
+/// Controls what is copied by [`duplicate_project`]. The defaults copy only the project and its
+/// tasks.
+#[derive(Debug, Default, Clone)]
+pub struct DuplicateProjectOptions {
+    /// Team for the new project; defaults to the team of the source project.
+    pub team: Option<String>,
+    pub members: bool,
+    pub notes: bool,
+    pub task_notes: bool,
+    pub task_assignee: bool,
+    pub task_attachments: bool,
+    pub task_dates: bool,
+    pub task_subtasks: bool,
+    pub task_tags: bool,
+    /// Shift task dates relative to a new start or due date.
+    pub schedule_dates: Option<ScheduleDates>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleDates {
+    pub should_skip_weekends: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_on: Option<Date>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_on: Option<Date>,
+}
+
+impl DuplicateProjectOptions {
+    /// Comma separated list for the `include` field of the duplicate request.
+    fn include(&self) -> String {
+        [
+            (self.members, "members"),
+            (self.notes, "notes"),
+            (self.task_notes, "task_notes"),
+            (self.task_assignee, "task_assignee"),
+            (self.task_attachments, "task_attachments"),
+            (self.task_dates, "task_dates"),
+            (self.task_subtasks, "task_subtasks"),
+            (self.task_tags, "task_tags"),
+        ]
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, name)| *name)
+        .collect::<Vec<_>>()
+        .join(",")
+    }
+}
+
+pub async fn get_project(
+    client: &impl asana::HTTPClient,
+    project_gid: &str,
+) -> Result<(ProjectResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("projects/{}", project_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<ProjectResponse>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn get_project_tasks(
+    client: &impl asana::HTTPClient,
+    project_gid: &str,
+    offset: Option<&str>,
+) -> Result<(types::ListResponse<types::Task>, serde_json::Value), crate::error::Error> {
+    let mut params = vec![("limit", asana::PAGE_LIMIT)];
+    if let Some(offset) = offset {
+        params.push(("offset", offset));
+    }
+    let path = format!("projects/{}/tasks", project_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<types::ListResponse<types::Task>>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+/// Start a server-side copy of a project. The returned job can be awaited with
+/// [`super::wait_for_job`], or use [`duplicate_project_and_wait`].
+pub async fn duplicate_project(
+    client: &impl asana::HTTPClient,
+    project_gid: &str,
+    name: &str,
+    options: &DuplicateProjectOptions,
+) -> Result<(super::JobResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("projects/{}/duplicate", project_gid);
+
+    let mut data = json!({
+        "name": name,
+        "include": options.include(),
+    });
+    if let Some(team) = &options.team {
+        data["team"] = json!(team);
+    }
+    if let Some(schedule_dates) = &options.schedule_dates {
+        data["schedule_dates"] = json!(schedule_dates);
+    }
+    let body = json!({ "data": data }).to_string();
+    let body = Some(body);
+
+    let result = match client
+        .send_request::<super::JobResponse>("POST", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "POST request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+/// Duplicate a project and wait for the copy to finish, returning the new project.
+pub async fn duplicate_project_and_wait(
+    client: &impl asana::HTTPClient,
+    project_gid: &str,
+    name: &str,
+    options: &DuplicateProjectOptions,
+    timeout: Duration,
+) -> Result<ProjectResponse, crate::error::Error> {
+    let (resp, _) = duplicate_project(client, project_gid, name, options).await?;
+    let job = super::wait_for_job(client, &resp.data.gid, timeout).await?;
+
+    let new_project = job.new_project.ok_or_else(|| {
+        crate::error::Error::new(format!(
+            "Job `gid: {}` finished without a new project",
+            job.gid
+        ))
+    })?;
+    let (project, _) = get_project(client, &new_project.gid).await?;
+
+    Ok(project)
+}
@@ -0,0 +1,192 @@
+use crate::asana::dates::{Date, DateTime};
+use crate::asana::types;
+use crate::asana::{self};
+
+/// Fields requested for each task returned by a search.
+const TASK_OPT_FIELDS: &str = "name,resource_subtype,notes,completed,completed_at,assignee.name,\
+due_on,due_at,start_on,created_at,modified_at,projects.name,tags.name,permalink_url";
+
+/// Condition applied to a custom field in a [`TaskSearch`].
+#[derive(Debug, Clone)]
+pub enum CustomFieldPredicate {
+    /// Exact match on a text, number or enum option gid.
+    Value(String),
+    IsSet(bool),
+    StartsWith(String),
+    EndsWith(String),
+    Contains(String),
+    LessThan(f64),
+    GreaterThan(f64),
+}
+
+/// Builder for the workspace advanced task search endpoint.
+///
+/// ```no_run
+/// # use asana_replicator_public::{asana::dates::Date, prelude::*};
+/// let overdue = TaskSearch::new("1234")
+///     .completed(false)
+///     .due_on_before(Date::today_in(&chrono::Utc));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TaskSearch {
+    workspace_gid: String,
+    params: Vec<(String, String)>,
+}
+
+impl TaskSearch {
+    pub fn new(workspace_gid: &str) -> Self {
+        Self {
+            workspace_gid: workspace_gid.to_string(),
+            params: Vec::new(),
+        }
+    }
+
+    fn param(mut self, key: &str, value: impl Into<String>) -> Self {
+        self.params.push((key.to_string(), value.into()));
+        self
+    }
+
+    pub fn text(self, text: &str) -> Self {
+        self.param("text", text)
+    }
+
+    pub fn resource_subtype(self, resource_subtype: &str) -> Self {
+        self.param("resource_subtype", resource_subtype)
+    }
+
+    /// Tasks assigned to any of `assignees`; `me` is accepted.
+    pub fn assignee_any(self, assignees: &[&str]) -> Self {
+        self.param("assignee.any", assignees.join(","))
+    }
+
+    pub fn assignee_not(self, assignees: &[&str]) -> Self {
+        self.param("assignee.not", assignees.join(","))
+    }
+
+    pub fn projects_any(self, projects: &[&str]) -> Self {
+        self.param("projects.any", projects.join(","))
+    }
+
+    pub fn projects_all(self, projects: &[&str]) -> Self {
+        self.param("projects.all", projects.join(","))
+    }
+
+    pub fn sections_any(self, sections: &[&str]) -> Self {
+        self.param("sections.any", sections.join(","))
+    }
+
+    pub fn tags_any(self, tags: &[&str]) -> Self {
+        self.param("tags.any", tags.join(","))
+    }
+
+    pub fn tags_all(self, tags: &[&str]) -> Self {
+        self.param("tags.all", tags.join(","))
+    }
+
+    /// Due strictly before `date`.
+    pub fn due_on_before(self, date: Date) -> Self {
+        self.param("due_on.before", date.to_string())
+    }
+
+    /// Due strictly after `date`.
+    pub fn due_on_after(self, date: Date) -> Self {
+        self.param("due_on.after", date.to_string())
+    }
+
+    pub fn modified_at_before(self, date_time: DateTime) -> Self {
+        self.param("modified_at.before", date_time.to_string())
+    }
+
+    pub fn modified_at_after(self, date_time: DateTime) -> Self {
+        self.param("modified_at.after", date_time.to_string())
+    }
+
+    pub fn completed(self, completed: bool) -> Self {
+        self.param("completed", completed.to_string())
+    }
+
+    pub fn is_subtask(self, is_subtask: bool) -> Self {
+        self.param("is_subtask", is_subtask.to_string())
+    }
+
+    pub fn custom_field(self, custom_field_gid: &str, predicate: CustomFieldPredicate) -> Self {
+        let (suffix, value) = match predicate {
+            CustomFieldPredicate::Value(value) => ("value", value),
+            CustomFieldPredicate::IsSet(is_set) => ("is_set", is_set.to_string()),
+            CustomFieldPredicate::StartsWith(value) => ("starts_with", value),
+            CustomFieldPredicate::EndsWith(value) => ("ends_with", value),
+            CustomFieldPredicate::Contains(value) => ("contains", value),
+            CustomFieldPredicate::LessThan(value) => ("less_than", value.to_string()),
+            CustomFieldPredicate::GreaterThan(value) => ("greater_than", value.to_string()),
+        };
+        let key = format!("custom_fields.{}.{}", custom_field_gid, suffix);
+
+        self.param(&key, value)
+    }
+}
+
+/// Fetch a single page of search results created before `created_before`, newest first.
+pub async fn search_tasks_page(
+    client: &impl asana::HTTPClient,
+    search: &TaskSearch,
+    created_before: Option<&str>,
+) -> Result<(types::ListResponse<types::Task>, serde_json::Value), crate::error::Error> {
+    let mut params: Vec<(&str, &str)> = search
+        .params
+        .iter()
+        .map(|(k, v)| (k.as_str(), v.as_str()))
+        .collect();
+    params.push(("sort_by", "created_at"));
+    params.push(("sort_ascending", "false"));
+    params.push(("limit", asana::PAGE_LIMIT));
+    params.push(("opt_fields", TASK_OPT_FIELDS));
+    if let Some(created_before) = created_before {
+        params.push(("created_at.before", created_before));
+    }
+    let path = format!("workspaces/{}/tasks/search", search.workspace_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<types::ListResponse<types::Task>>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+/// Run a search and collect every matching task.
+///
+/// The search endpoint has no offset pagination, so pages are walked by `created_at`; tasks
+/// sharing the exact creation timestamp of a page boundary may be missed.
+pub async fn search_tasks(
+    client: &impl asana::HTTPClient,
+    search: &TaskSearch,
+) -> Result<Vec<types::Task>, crate::error::Error> {
+    let page_limit: usize = asana::PAGE_LIMIT.parse()?;
+    let mut tasks: Vec<types::Task> = Vec::new();
+    let mut created_before: Option<String> = None;
+
+    loop {
+        let (page, _) = search_tasks_page(client, search, created_before.as_deref()).await?;
+        let page_len = page.data.len();
+        created_before = page
+            .data
+            .last()
+            .and_then(|task| task.created_at.map(|created_at| created_at.to_string()));
+        tasks.extend(page.data);
+
+        if page_len < page_limit || created_before.is_none() {
+            break;
+        }
+    }
+
+    Ok(tasks)
+}
@@ -0,0 +1,202 @@
+use crate::asana::dates::DateTime;
+use crate::asana::types;
+use crate::asana::{self};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Fields requested when listing status updates; list responses are compact records otherwise.
+const STATUS_UPDATE_OPT_FIELDS: &str = "resource_subtype,title,text,html_text,status_type,author,author.name,created_at,created_by,created_by.name,modified_at,parent,parent.name";
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct StatusUpdateResponse {
+    pub data: types::StatusUpdate,
+}
+
+/// Content of a new status update.
+#[derive(Debug, Serialize)]
+pub struct CreateStatusUpdate {
+    pub status_type: types::StatusType,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub html_text: Option<String>,
+}
+
+/// List status updates for a project, portfolio or goal, newest first.
+pub async fn get_status_updates(
+    client: &impl asana::HTTPClient,
+    parent_gid: &str,
+    created_since: Option<DateTime>,
+    offset: Option<&str>,
+) -> Result<(types::ListResponse<types::StatusUpdate>, serde_json::Value), crate::error::Error> {
+    let created_since = created_since.map(|created_since| created_since.to_string());
+    let mut params = vec![
+        ("parent", parent_gid),
+        ("limit", asana::PAGE_LIMIT),
+        ("opt_fields", STATUS_UPDATE_OPT_FIELDS),
+    ];
+    if let Some(created_since) = &created_since {
+        params.push(("created_since", created_since.as_str()));
+    }
+    if let Some(offset) = offset {
+        params.push(("offset", offset));
+    }
+    let path = String::from("status_updates");
+
+    let body = None;
+    let result = match client
+        .send_request::<types::ListResponse<types::StatusUpdate>>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn get_status_update(
+    client: &impl asana::HTTPClient,
+    status_update_gid: &str,
+) -> Result<(StatusUpdateResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("status_updates/{}", status_update_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<StatusUpdateResponse>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn create_status_update(
+    client: &impl asana::HTTPClient,
+    parent_gid: &str,
+    status_update: &CreateStatusUpdate,
+) -> Result<(StatusUpdateResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = String::from("status_updates");
+
+    let mut data = json!(status_update);
+    data["parent"] = json!(parent_gid);
+    let body = json!({ "data": data }).to_string();
+    let body = Some(body);
+
+    let result = match client
+        .send_request::<StatusUpdateResponse>("POST", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "POST request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn delete_status_update(
+    client: &impl asana::HTTPClient,
+    status_update_gid: &str,
+) -> Result<(types::EmptyResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("status_updates/{}", status_update_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<types::EmptyResponse>("DELETE", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "DELETE request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use serde_json::Value;
+    use std::sync::Mutex;
+
+    /// Answers every list request with a compact status update, recording the query parameters.
+    #[derive(Default)]
+    struct Asana {
+        params: Mutex<Vec<(String, String)>>,
+    }
+
+    #[async_trait]
+    impl asana::HTTPClient for Asana {
+        async fn send_request<T>(
+            &self,
+            _method: &str,
+            _endpoint: &str,
+            params: &[(&str, &str)],
+            _body: Option<String>,
+        ) -> Result<(T, Value), crate::error::Error>
+        where
+            T: serde::de::DeserializeOwned + std::fmt::Debug,
+        {
+            *self.params.lock().unwrap() = params
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect();
+            let value = json!({
+                "data": [{
+                    "gid": "12345",
+                    "resource_type": "status_update",
+                    "resource_subtype": "project_status_update",
+                    "title": "Week 42"
+                }],
+                "next_page": null
+            });
+
+            Ok((serde_json::from_value(value.clone())?, value))
+        }
+    }
+
+    #[tokio::test]
+    async fn lists_compact_status_updates() {
+        let client = Asana::default();
+        let (list, _) = get_status_updates(&client, "1200", None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(list.data.len(), 1);
+        assert_eq!(list.data[0].title.as_deref(), Some("Week 42"));
+        assert!(list.data[0].status_type.is_none());
+
+        let params = client.params.lock().unwrap();
+        let opt_fields = params
+            .iter()
+            .find(|(key, _)| key == "opt_fields")
+            .map(|(_, value)| value.as_str());
+        assert!(opt_fields.is_some_and(|fields| fields.split(',').any(|f| f == "status_type")));
+    }
+}
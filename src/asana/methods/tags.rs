@@ -0,0 +1,241 @@
+use crate::asana::types;
+use crate::asana::{self};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TagResponse {
+    pub data: types::Tag,
+}
+
+pub async fn get_tags(
+    client: &impl asana::HTTPClient,
+    workspace_gid: &str,
+    offset: Option<&str>,
+) -> Result<(types::ListResponse<types::Tag>, serde_json::Value), crate::error::Error> {
+    let mut params = vec![("limit", asana::PAGE_LIMIT)];
+    if let Some(offset) = offset {
+        params.push(("offset", offset));
+    }
+    let path = format!("workspaces/{}/tags", workspace_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<types::ListResponse<types::Tag>>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn get_tag(
+    client: &impl asana::HTTPClient,
+    tag_gid: &str,
+) -> Result<(TagResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("tags/{}", tag_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<TagResponse>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn get_task_tags(
+    client: &impl asana::HTTPClient,
+    task_gid: &str,
+    offset: Option<&str>,
+) -> Result<(types::ListResponse<types::Tag>, serde_json::Value), crate::error::Error> {
+    let mut params = vec![("limit", asana::PAGE_LIMIT)];
+    if let Some(offset) = offset {
+        params.push(("offset", offset));
+    }
+    let path = format!("tasks/{}/tags", task_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<types::ListResponse<types::Tag>>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn create_tag(
+    client: &impl asana::HTTPClient,
+    workspace_gid: &str,
+    name: &str,
+    color: Option<types::Color>,
+) -> Result<(TagResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("workspaces/{}/tags", workspace_gid);
+
+    let mut data = json!({ "name": name });
+    if let Some(color) = color {
+        data["color"] = json!(color.as_str());
+    }
+    let body = json!({ "data": data }).to_string();
+    let body = Some(body);
+
+    let result = match client
+        .send_request::<TagResponse>("POST", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "POST request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn rename_tag(
+    client: &impl asana::HTTPClient,
+    tag_gid: &str,
+    name: &str,
+) -> Result<(TagResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("tags/{}", tag_gid);
+
+    let body = json!({
+        "data": {
+            "name": name,
+        }
+    })
+    .to_string();
+    let body = Some(body);
+
+    let result = match client
+        .send_request::<TagResponse>("PUT", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "PUT request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn delete_tag(
+    client: &impl asana::HTTPClient,
+    tag_gid: &str,
+) -> Result<(types::EmptyResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("tags/{}", tag_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<types::EmptyResponse>("DELETE", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "DELETE request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn add_tag_to_task(
+    client: &impl asana::HTTPClient,
+    task_gid: &str,
+    tag_gid: &str,
+) -> Result<(types::EmptyResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("tasks/{}/addTag", task_gid);
+
+    let body = json!({
+        "data": {
+            "tag": tag_gid,
+        }
+    })
+    .to_string();
+    let body = Some(body);
+
+    let result = match client
+        .send_request::<types::EmptyResponse>("POST", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "POST request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn remove_tag_from_task(
+    client: &impl asana::HTTPClient,
+    task_gid: &str,
+    tag_gid: &str,
+) -> Result<(types::EmptyResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("tasks/{}/removeTag", task_gid);
+
+    let body = json!({
+        "data": {
+            "tag": tag_gid,
+        }
+    })
+    .to_string();
+    let body = Some(body);
+
+    let result = match client
+        .send_request::<types::EmptyResponse>("POST", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "POST request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
@@ -0,0 +1,31 @@
+use crate::asana::types;
+use crate::asana::{self};
+
+/// Direct subtasks of a task; subtasks of those must be fetched in turn.
+pub async fn get_subtasks(
+    client: &impl asana::HTTPClient,
+    task_gid: &str,
+    offset: Option<&str>,
+) -> Result<(types::ListResponse<types::Task>, serde_json::Value), crate::error::Error> {
+    let mut params = vec![("limit", asana::PAGE_LIMIT)];
+    if let Some(offset) = offset {
+        params.push(("offset", offset));
+    }
+    let path = format!("tasks/{}/subtasks", task_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<types::ListResponse<types::Task>>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
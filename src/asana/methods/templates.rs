@@ -0,0 +1,117 @@
+use crate::asana::types;
+use crate::asana::{self};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ProjectTemplateResponse {
+    pub data: types::ProjectTemplate,
+}
+
+/// Value supplied for one of a template's `requested_dates` or `requested_roles`.
+#[derive(Debug, Serialize)]
+pub struct TemplateVariableValue {
+    pub gid: String,
+    pub value: String,
+}
+
+pub async fn get_project_templates(
+    client: &impl asana::HTTPClient,
+    team_gid: &str,
+    offset: Option<&str>,
+) -> Result<
+    (
+        types::ListResponse<types::ProjectTemplate>,
+        serde_json::Value,
+    ),
+    crate::error::Error,
+> {
+    let mut params = vec![("limit", asana::PAGE_LIMIT)];
+    if let Some(offset) = offset {
+        params.push(("offset", offset));
+    }
+    let path = format!("teams/{}/project_templates", team_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<types::ListResponse<types::ProjectTemplate>>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn get_project_template(
+    client: &impl asana::HTTPClient,
+    project_template_gid: &str,
+) -> Result<(ProjectTemplateResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("project_templates/{}", project_template_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<ProjectTemplateResponse>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+/// Create a project in `team_gid` from a template. The returned job can be awaited with
+/// [`super::wait_for_job`].
+pub async fn instantiate_project_template(
+    client: &impl asana::HTTPClient,
+    project_template_gid: &str,
+    team_gid: &str,
+    name: &str,
+    requested_dates: &[TemplateVariableValue],
+    requested_roles: &[TemplateVariableValue],
+) -> Result<(super::JobResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!(
+        "project_templates/{}/instantiateProject",
+        project_template_gid
+    );
+
+    let body = json!({
+        "data": {
+            "name": name,
+            "team": team_gid,
+            "requested_dates": requested_dates,
+            "requested_roles": requested_roles,
+        }
+    })
+    .to_string();
+    let body = Some(body);
+
+    let result = match client
+        .send_request::<super::JobResponse>("POST", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "POST request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
@@ -0,0 +1,253 @@
+use crate::asana::dates::Date;
+use crate::asana::types;
+use crate::asana::{self};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TimeTrackingEntryResponse {
+    pub data: types::TimeTrackingEntry,
+}
+
+/// Asana records time in whole minutes; partial minutes are rounded down.
+fn duration_minutes(duration: Duration) -> u64 {
+    duration.as_secs() / 60
+}
+
+pub async fn get_time_tracking_entries(
+    client: &impl asana::HTTPClient,
+    task_gid: &str,
+    offset: Option<&str>,
+) -> Result<
+    (
+        types::ListResponse<types::TimeTrackingEntry>,
+        serde_json::Value,
+    ),
+    crate::error::Error,
+> {
+    let mut params = vec![
+        ("limit", asana::PAGE_LIMIT),
+        (
+            "opt_fields",
+            "duration_minutes,entered_on,created_at,created_by.name,task.name",
+        ),
+    ];
+    if let Some(offset) = offset {
+        params.push(("offset", offset));
+    }
+    let path = format!("tasks/{}/time_tracking_entries", task_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<types::ListResponse<types::TimeTrackingEntry>>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn get_time_tracking_entry(
+    client: &impl asana::HTTPClient,
+    time_tracking_entry_gid: &str,
+) -> Result<(TimeTrackingEntryResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("time_tracking_entries/{}", time_tracking_entry_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<TimeTrackingEntryResponse>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+/// Log time against a task on the given day.
+pub async fn create_time_tracking_entry(
+    client: &impl asana::HTTPClient,
+    task_gid: &str,
+    duration: Duration,
+    entered_on: Date,
+) -> Result<(TimeTrackingEntryResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("tasks/{}/time_tracking_entries", task_gid);
+
+    let body = json!({
+        "data": {
+            "duration_minutes": duration_minutes(duration),
+            "entered_on": entered_on,
+        }
+    })
+    .to_string();
+    let body = Some(body);
+
+    let result = match client
+        .send_request::<TimeTrackingEntryResponse>("POST", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "POST request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+/// Change the duration and/or date of an entry; `None` leaves the field untouched.
+pub async fn update_time_tracking_entry(
+    client: &impl asana::HTTPClient,
+    time_tracking_entry_gid: &str,
+    duration: Option<Duration>,
+    entered_on: Option<Date>,
+) -> Result<(TimeTrackingEntryResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("time_tracking_entries/{}", time_tracking_entry_gid);
+
+    let mut data = json!({});
+    if let Some(duration) = duration {
+        data["duration_minutes"] = json!(duration_minutes(duration));
+    }
+    if let Some(entered_on) = entered_on {
+        data["entered_on"] = json!(entered_on);
+    }
+    let body = json!({ "data": data }).to_string();
+    let body = Some(body);
+
+    let result = match client
+        .send_request::<TimeTrackingEntryResponse>("PUT", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "PUT request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn delete_time_tracking_entry(
+    client: &impl asana::HTTPClient,
+    time_tracking_entry_gid: &str,
+) -> Result<(types::EmptyResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("time_tracking_entries/{}", time_tracking_entry_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<types::EmptyResponse>("DELETE", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "DELETE request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+/// Collect every time tracking entry on a task across all pages.
+pub async fn get_all_time_tracking_entries(
+    client: &impl asana::HTTPClient,
+    task_gid: &str,
+) -> Result<Vec<types::TimeTrackingEntry>, crate::error::Error> {
+    let mut entries = Vec::new();
+    let mut offset: Option<String> = None;
+
+    loop {
+        let (page, _) = get_time_tracking_entries(client, task_gid, offset.as_deref()).await?;
+        entries.extend(page.data);
+
+        match page.next_page {
+            Some(next_page) => offset = Some(next_page.offset),
+            None => break,
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Total time logged against a task.
+pub async fn task_time_total(
+    client: &impl asana::HTTPClient,
+    task_gid: &str,
+) -> Result<Duration, crate::error::Error> {
+    let entries = get_all_time_tracking_entries(client, task_gid).await?;
+
+    Ok(entries.iter().map(|entry| entry.duration()).sum())
+}
+
+/// Total time logged per task in a project, keyed by task gid. Subtasks at any depth are
+/// included under their own gid, since Asana does not list them as project tasks. Tasks without
+/// entries are omitted.
+pub async fn project_time_totals(
+    client: &impl asana::HTTPClient,
+    project_gid: &str,
+) -> Result<HashMap<String, Duration>, crate::error::Error> {
+    let mut totals = HashMap::new();
+    let mut pending: Vec<String> = Vec::new();
+    let mut offset: Option<String> = None;
+
+    loop {
+        let (page, _) = super::get_project_tasks(client, project_gid, offset.as_deref()).await?;
+        pending.extend(page.data.into_iter().map(|task| task.gid));
+
+        match page.next_page {
+            Some(next_page) => offset = Some(next_page.offset),
+            None => break,
+        }
+    }
+
+    while let Some(task_gid) = pending.pop() {
+        // A subtask can also be a project task; count it once.
+        if totals.contains_key(&task_gid) {
+            continue;
+        }
+
+        let mut offset: Option<String> = None;
+        loop {
+            let (page, _) = super::get_subtasks(client, &task_gid, offset.as_deref()).await?;
+            pending.extend(page.data.into_iter().map(|task| task.gid));
+
+            match page.next_page {
+                Some(next_page) => offset = Some(next_page.offset),
+                None => break,
+            }
+        }
+
+        let total = task_time_total(client, &task_gid).await?;
+        totals.insert(task_gid, total);
+    }
+    totals.retain(|_, total| !total.is_zero());
+
+    Ok(totals)
+}
@@ -0,0 +1,77 @@
+use crate::asana::types;
+use crate::asana::{self};
+
+/// Kinds of object the typeahead endpoint can look up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeaheadResourceType {
+    User,
+    Project,
+    Tag,
+    Task,
+    Portfolio,
+}
+
+impl TypeaheadResourceType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TypeaheadResourceType::User => "user",
+            TypeaheadResourceType::Project => "project",
+            TypeaheadResourceType::Tag => "tag",
+            TypeaheadResourceType::Task => "task",
+            TypeaheadResourceType::Portfolio => "portfolio",
+        }
+    }
+}
+
+pub async fn typeahead(
+    client: &impl asana::HTTPClient,
+    workspace_gid: &str,
+    resource_type: TypeaheadResourceType,
+    query: &str,
+    count: Option<u32>,
+) -> Result<
+    (
+        types::ListResponse<types::TypeaheadResult>,
+        serde_json::Value,
+    ),
+    crate::error::Error,
+> {
+    let count = count.map(|count| count.to_string());
+    let mut params = vec![("resource_type", resource_type.as_str()), ("query", query)];
+    if let Some(count) = &count {
+        params.push(("count", count.as_str()));
+    }
+    let path = format!("workspaces/{}/typeahead", workspace_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<types::ListResponse<types::TypeaheadResult>>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+/// Resolve a human-friendly name to a gid, requiring an exact (case-sensitive) name match.
+pub async fn resolve_gid(
+    client: &impl asana::HTTPClient,
+    workspace_gid: &str,
+    resource_type: TypeaheadResourceType,
+    name: &str,
+) -> Result<Option<String>, crate::error::Error> {
+    let (resp, _) = typeahead(client, workspace_gid, resource_type, name, Some(100)).await?;
+
+    Ok(resp
+        .data
+        .into_iter()
+        .find(|result| result.name() == name)
+        .map(|result| result.gid().to_string()))
+}
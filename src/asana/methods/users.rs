@@ -0,0 +1,303 @@
+use crate::asana::types;
+use crate::asana::{self};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Fields requested for users so that the extended [`types::User`] model is populated.
+const USER_OPT_FIELDS: &str = "name,email,photo,workspaces,workspaces.name";
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UserResponse {
+    pub data: types::User,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TeamMembershipResponse {
+    pub data: types::TeamMembership,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MembershipResponse {
+    pub data: types::Membership,
+}
+
+pub async fn get_workspace_users(
+    client: &impl asana::HTTPClient,
+    workspace_gid: &str,
+    offset: Option<&str>,
+) -> Result<(types::ListResponse<types::User>, serde_json::Value), crate::error::Error> {
+    let mut params = vec![
+        ("limit", asana::PAGE_LIMIT),
+        ("opt_fields", USER_OPT_FIELDS),
+    ];
+    if let Some(offset) = offset {
+        params.push(("offset", offset));
+    }
+    let path = format!("workspaces/{}/users", workspace_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<types::ListResponse<types::User>>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn get_team_users(
+    client: &impl asana::HTTPClient,
+    team_gid: &str,
+    offset: Option<&str>,
+) -> Result<(types::ListResponse<types::User>, serde_json::Value), crate::error::Error> {
+    let mut params = vec![
+        ("limit", asana::PAGE_LIMIT),
+        ("opt_fields", USER_OPT_FIELDS),
+    ];
+    if let Some(offset) = offset {
+        params.push(("offset", offset));
+    }
+    let path = format!("teams/{}/users", team_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<types::ListResponse<types::User>>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+/// Fetch a single user; `user` may be a gid, an email address or the string `me`.
+pub async fn get_user(
+    client: &impl asana::HTTPClient,
+    user: &str,
+) -> Result<(UserResponse, serde_json::Value), crate::error::Error> {
+    let params = vec![("opt_fields", USER_OPT_FIELDS)];
+    let path = format!("users/{}", user);
+
+    let body = None;
+    let result = match client
+        .send_request::<UserResponse>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn get_user_by_email(
+    client: &impl asana::HTTPClient,
+    email: &str,
+) -> Result<(UserResponse, serde_json::Value), crate::error::Error> {
+    get_user(client, email).await
+}
+
+pub async fn add_team_member(
+    client: &impl asana::HTTPClient,
+    team_gid: &str,
+    user: &str,
+) -> Result<(TeamMembershipResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("teams/{}/addUser", team_gid);
+
+    let body = json!({
+        "data": {
+            "user": user,
+        }
+    })
+    .to_string();
+    let body = Some(body);
+
+    let result = match client
+        .send_request::<TeamMembershipResponse>("POST", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "POST request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn remove_team_member(
+    client: &impl asana::HTTPClient,
+    team_gid: &str,
+    user: &str,
+) -> Result<(types::EmptyResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("teams/{}/removeUser", team_gid);
+
+    let body = json!({
+        "data": {
+            "user": user,
+        }
+    })
+    .to_string();
+    let body = Some(body);
+
+    let result = match client
+        .send_request::<types::EmptyResponse>("POST", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "POST request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn get_project_memberships(
+    client: &impl asana::HTTPClient,
+    project_gid: &str,
+    offset: Option<&str>,
+) -> Result<(types::ListResponse<types::Membership>, serde_json::Value), crate::error::Error> {
+    let mut params = vec![
+        ("parent", project_gid),
+        ("limit", asana::PAGE_LIMIT),
+        ("opt_fields", "parent.name,member.name,access_level"),
+    ];
+    if let Some(offset) = offset {
+        params.push(("offset", offset));
+    }
+    let path = String::from("memberships");
+
+    let body = None;
+    let result = match client
+        .send_request::<types::ListResponse<types::Membership>>("GET", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "GET request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn add_project_member(
+    client: &impl asana::HTTPClient,
+    project_gid: &str,
+    member_gid: &str,
+    access_level: types::AccessLevel,
+) -> Result<(MembershipResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = String::from("memberships");
+
+    let body = json!({
+        "data": {
+            "parent": project_gid,
+            "member": member_gid,
+            "access_level": access_level.as_str(),
+        }
+    })
+    .to_string();
+    let body = Some(body);
+
+    let result = match client
+        .send_request::<MembershipResponse>("POST", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "POST request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn update_project_membership(
+    client: &impl asana::HTTPClient,
+    membership_gid: &str,
+    access_level: types::AccessLevel,
+) -> Result<(MembershipResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("memberships/{}", membership_gid);
+
+    let body = json!({
+        "data": {
+            "access_level": access_level.as_str(),
+        }
+    })
+    .to_string();
+    let body = Some(body);
+
+    let result = match client
+        .send_request::<MembershipResponse>("PUT", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "PUT request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+pub async fn remove_project_membership(
+    client: &impl asana::HTTPClient,
+    membership_gid: &str,
+) -> Result<(types::EmptyResponse, serde_json::Value), crate::error::Error> {
+    let params: Vec<(&str, &str)> = Vec::new();
+    let path = format!("memberships/{}", membership_gid);
+
+    let body = None;
+    let result = match client
+        .send_request::<types::EmptyResponse>("DELETE", &path, &params, body)
+        .await
+    {
+        Ok(value) => value,
+        Err(err) => {
+            return Err(crate::error::Error::new(format!(
+                "DELETE request failed: {}",
+                err
+            )))
+        }
+    };
+
+    Ok(result)
+}
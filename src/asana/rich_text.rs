@@ -0,0 +1,957 @@
+//! Asana rich text
+//!
+//! Asana returns formatted text (`html_notes`, `html_description`, `html_text`) as a restricted
+//! XML dialect wrapped in `<body>`. This module parses it into a small AST, renders the AST to
+//! Markdown, plain text or back to rich text, and parses Markdown into the same AST.
+
+use crate::error::Error;
+use std::iter::Peekable;
+use std::vec::IntoIter;
+
+/// URL scheme used in Markdown for mention gids, either as the link target or, when the mention
+/// also has an `href`, as the link title: `[text](href "asana:gid")`.
+const ASANA_SCHEME: &str = "asana:";
+
+/// A node of an Asana rich text document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    Text(String),
+    Strong(Vec<Node>),
+    Emphasis(Vec<Node>),
+    Underline(Vec<Node>),
+    Strikethrough(Vec<Node>),
+    Code(String),
+    /// A link; `gid` is set for `<a data-asana-gid>` mentions of users, tasks and projects.
+    Link {
+        href: Option<String>,
+        gid: Option<String>,
+        children: Vec<Node>,
+    },
+    List {
+        ordered: bool,
+        items: Vec<Vec<Node>>,
+    },
+    CodeBlock(String),
+    Blockquote(Vec<Node>),
+    Heading {
+        level: u8,
+        children: Vec<Node>,
+    },
+    HorizontalRule,
+}
+
+#[derive(Debug)]
+enum Token {
+    Open {
+        name: String,
+        attrs: Vec<(String, String)>,
+        self_closing: bool,
+    },
+    Close(String),
+    Text(String),
+}
+
+/// Parse Asana rich text into nodes. Unknown tags such as `<body>` are transparent.
+pub fn parse(rich_text: &str) -> Result<Vec<Node>, Error> {
+    let mut tokens = tokenize(rich_text)?.into_iter().peekable();
+
+    build(&mut tokens, None)
+}
+
+/// Render Asana rich text as Markdown. Underline has no Markdown equivalent and is dropped.
+pub fn to_markdown(nodes: &[Node]) -> String {
+    let mut out = String::new();
+    render_markdown(nodes, &mut out);
+
+    out.trim_end_matches('\n').to_string()
+}
+
+/// Render the text content only, keeping list markers and line structure.
+pub fn to_plain_text(nodes: &[Node]) -> String {
+    let mut out = String::new();
+    render_plain_text(nodes, &mut out);
+
+    out.trim_end_matches('\n').to_string()
+}
+
+/// Render nodes as a valid Asana rich text document.
+pub fn to_rich_text(nodes: &[Node]) -> String {
+    let mut out = String::from("<body>");
+    render_rich_text(nodes, &mut out);
+    out.push_str("</body>");
+
+    out
+}
+
+/// Parse Markdown (headings, lists, code, quotes, emphasis and links) into nodes.
+pub fn from_markdown(markdown: &str) -> Vec<Node> {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut nodes = parse_blocks(&lines);
+
+    if let Some(Node::Text(text)) = nodes.last_mut() {
+        while text.ends_with('\n') {
+            text.pop();
+        }
+        if text.is_empty() {
+            nodes.pop();
+        }
+    }
+
+    nodes
+}
+
+/// Convert Asana rich text to Markdown.
+pub fn rich_text_to_markdown(rich_text: &str) -> Result<String, Error> {
+    Ok(to_markdown(&parse(rich_text)?))
+}
+
+/// Convert Markdown to Asana rich text.
+pub fn markdown_to_rich_text(markdown: &str) -> String {
+    to_rich_text(&from_markdown(markdown))
+}
+
+// Rich text parsing
+
+fn tokenize(input: &str) -> Result<Vec<Token>, Error> {
+    let mut tokens = Vec::new();
+    let mut rest = input;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            tokens.push(Token::Text(decode_entities(rest)));
+            break;
+        };
+        if start > 0 {
+            tokens.push(Token::Text(decode_entities(&rest[..start])));
+        }
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let end = comment
+                .find("-->")
+                .ok_or_else(|| Error::new("Unterminated comment in rich text"))?;
+            rest = &comment[end + 3..];
+            continue;
+        }
+
+        let end = rest
+            .find('>')
+            .ok_or_else(|| Error::new(format!("Unterminated tag in rich text: {}", rest)))?;
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(Token::Close(name.trim().to_lowercase()));
+        } else {
+            tokens.push(parse_open_tag(tag)?);
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn parse_open_tag(tag: &str) -> Result<Token, Error> {
+    let (tag, self_closing) = match tag.trim_end().strip_suffix('/') {
+        Some(tag) => (tag, true),
+        None => (tag, false),
+    };
+    let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+    let name = tag[..name_end].to_lowercase();
+    if name.is_empty() {
+        return Err(Error::new(format!(
+            "Empty tag name in rich text: <{}>",
+            tag
+        )));
+    }
+
+    let mut attrs = Vec::new();
+    let mut rest = tag[name_end..].trim_start();
+    while !rest.is_empty() {
+        let key_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let key = rest[..key_end].to_lowercase();
+        rest = rest[key_end..].trim_start();
+
+        let Some(value_part) = rest.strip_prefix('=') else {
+            attrs.push((key, String::new()));
+            continue;
+        };
+        let value_part = value_part.trim_start();
+        let quote = value_part
+            .chars()
+            .next()
+            .filter(|c| *c == '"' || *c == '\'')
+            .ok_or_else(|| Error::new(format!("Unquoted attribute `{}` in rich text", key)))?;
+        let value_end = value_part[1..]
+            .find(quote)
+            .ok_or_else(|| Error::new(format!("Unterminated attribute `{}` in rich text", key)))?;
+        attrs.push((key, decode_entities(&value_part[1..value_end + 1])));
+        rest = value_part[value_end + 2..].trim_start();
+    }
+
+    Ok(Token::Open {
+        name,
+        attrs,
+        self_closing,
+    })
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+
+    out
+}
+
+fn attr(attrs: &[(String, String)], key: &str) -> Option<String> {
+    attrs.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone())
+}
+
+fn build(
+    tokens: &mut Peekable<IntoIter<Token>>,
+    closing: Option<&str>,
+) -> Result<Vec<Node>, Error> {
+    let mut nodes = Vec::new();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Text(text) => push_text(&mut nodes, &text),
+            Token::Close(name) => {
+                return match closing {
+                    Some(expected) if expected == name => Ok(nodes),
+                    _ => Err(Error::new(format!(
+                        "Unexpected closing tag `</{}>` in rich text",
+                        name
+                    ))),
+                }
+            }
+            Token::Open {
+                name,
+                attrs,
+                self_closing,
+            } => {
+                let children = if self_closing {
+                    Vec::new()
+                } else if name == "hr" {
+                    // `<hr>` is a void element; tolerate an explicit `</hr>` as well
+                    if matches!(tokens.peek(), Some(Token::Close(close)) if close == "hr") {
+                        tokens.next();
+                    }
+                    Vec::new()
+                } else if name == "ul" || name == "ol" {
+                    let items = build_list(tokens, &name)?;
+                    nodes.push(Node::List {
+                        ordered: name == "ol",
+                        items,
+                    });
+                    continue;
+                } else {
+                    build(tokens, Some(&name))?
+                };
+
+                match name.as_str() {
+                    "strong" | "b" => nodes.push(Node::Strong(children)),
+                    "em" | "i" => nodes.push(Node::Emphasis(children)),
+                    "u" => nodes.push(Node::Underline(children)),
+                    "s" | "del" | "strike" => nodes.push(Node::Strikethrough(children)),
+                    "code" => nodes.push(Node::Code(to_plain_text(&children))),
+                    "pre" => nodes.push(Node::CodeBlock(to_plain_text(&children))),
+                    "blockquote" => nodes.push(Node::Blockquote(children)),
+                    "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => nodes.push(Node::Heading {
+                        level: name[1..].parse().unwrap_or(1),
+                        children,
+                    }),
+                    "hr" => nodes.push(Node::HorizontalRule),
+                    "a" => nodes.push(Node::Link {
+                        href: attr(&attrs, "href"),
+                        gid: attr(&attrs, "data-asana-gid"),
+                        children,
+                    }),
+                    "ul" | "ol" => nodes.push(Node::List {
+                        ordered: name == "ol",
+                        items: Vec::new(),
+                    }),
+                    // `<body>`, `<li>` outside a list and anything unrecognised are transparent
+                    _ => {
+                        for child in children {
+                            match child {
+                                Node::Text(text) => push_text(&mut nodes, &text),
+                                child => nodes.push(child),
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    match closing {
+        Some(name) => Err(Error::new(format!(
+            "Unclosed tag `<{}>` in rich text",
+            name
+        ))),
+        None => Ok(nodes),
+    }
+}
+
+fn build_list(tokens: &mut Peekable<IntoIter<Token>>, name: &str) -> Result<Vec<Vec<Node>>, Error> {
+    let mut items = Vec::new();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Close(close) if close == name => return Ok(items),
+            Token::Text(text) if text.trim().is_empty() => {}
+            Token::Open {
+                name: open,
+                self_closing,
+                ..
+            } if open == "li" => {
+                if self_closing {
+                    items.push(Vec::new());
+                } else {
+                    items.push(build(tokens, Some("li"))?);
+                }
+            }
+            token => {
+                return Err(Error::new(format!(
+                    "Unexpected {:?} inside `<{}>` in rich text",
+                    token, name
+                )))
+            }
+        }
+    }
+
+    Err(Error::new(format!(
+        "Unclosed tag `<{}>` in rich text",
+        name
+    )))
+}
+
+fn push_text(nodes: &mut Vec<Node>, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    match nodes.last_mut() {
+        Some(Node::Text(last)) => last.push_str(text),
+        _ => nodes.push(Node::Text(text.to_string())),
+    }
+}
+
+// Rendering
+
+fn ensure_line_start(out: &mut String) {
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+/// Escape inline markup, and block markers (`#`, `-`, `+`, `>`, `1.`) at the start of a line so
+/// that text is not re-parsed as a heading, list or quote.
+fn escape_markdown(text: &str, mut line_start: bool) -> String {
+    let mut out = String::with_capacity(text.len());
+    for line in text.split_inclusive('\n') {
+        let mut rest = line;
+        if line_start {
+            let body = rest.trim_start_matches([' ', '\t']);
+            out.push_str(&rest[..rest.len() - body.len()]);
+            rest = body;
+
+            let digits = body.chars().take_while(char::is_ascii_digit).count();
+            if body.starts_with(['#', '-', '+', '>']) {
+                out.push('\\');
+            } else if digits > 0 && body[digits..].starts_with('.') {
+                out.push_str(&body[..digits]);
+                out.push('\\');
+                rest = &body[digits..];
+            }
+        }
+        for c in rest.chars() {
+            if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '~') {
+                out.push('\\');
+            }
+            out.push(c);
+        }
+        line_start = rest.ends_with('\n');
+    }
+
+    out
+}
+
+fn indent_lines(text: &str, first: &str, rest: &str) -> String {
+    text.lines()
+        .enumerate()
+        .map(|(index, line)| {
+            let prefix = if index == 0 { first } else { rest };
+            format!("{}{}\n", prefix, line)
+        })
+        .collect()
+}
+
+fn render_markdown(nodes: &[Node], out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => {
+                let line_start = out.is_empty() || out.ends_with('\n');
+                out.push_str(&escape_markdown(text, line_start));
+            }
+            Node::Strong(children) => {
+                out.push_str("**");
+                render_markdown(children, out);
+                out.push_str("**");
+            }
+            Node::Emphasis(children) => {
+                out.push('*');
+                render_markdown(children, out);
+                out.push('*');
+            }
+            Node::Underline(children) => render_markdown(children, out),
+            Node::Strikethrough(children) => {
+                out.push_str("~~");
+                render_markdown(children, out);
+                out.push_str("~~");
+            }
+            Node::Code(code) => {
+                if code.contains('`') {
+                    out.push_str(&format!("`` {} ``", code));
+                } else {
+                    out.push_str(&format!("`{}`", code));
+                }
+            }
+            Node::Link {
+                href,
+                gid,
+                children,
+            } => {
+                let target = match (href, gid) {
+                    (Some(href), Some(gid)) => format!("{} \"{}{}\"", href, ASANA_SCHEME, gid),
+                    (Some(href), None) => href.clone(),
+                    (None, Some(gid)) => format!("{}{}", ASANA_SCHEME, gid),
+                    (None, None) => String::new(),
+                };
+                let mut text = String::new();
+                render_markdown(children, &mut text);
+                if text.is_empty() {
+                    text = gid.clone().unwrap_or_else(|| target.clone());
+                }
+                out.push_str(&format!("[{}]({})", text, target));
+            }
+            Node::List { ordered, items } => {
+                ensure_line_start(out);
+                for (index, item) in items.iter().enumerate() {
+                    let marker = if *ordered {
+                        format!("{}. ", index + 1)
+                    } else {
+                        String::from("- ")
+                    };
+                    let mut content = String::new();
+                    render_markdown(item, &mut content);
+                    if content.is_empty() {
+                        content.push('\n');
+                    }
+                    out.push_str(&indent_lines(&content, &marker, &" ".repeat(marker.len())));
+                }
+            }
+            Node::CodeBlock(code) => {
+                ensure_line_start(out);
+                out.push_str("```\n");
+                out.push_str(code);
+                ensure_line_start(out);
+                out.push_str("```\n");
+            }
+            Node::Blockquote(children) => {
+                ensure_line_start(out);
+                let mut content = String::new();
+                render_markdown(children, &mut content);
+                out.push_str(&indent_lines(content.trim_end_matches('\n'), "> ", "> "));
+            }
+            Node::Heading { level, children } => {
+                ensure_line_start(out);
+                out.push_str(&"#".repeat((*level).clamp(1, 6) as usize));
+                out.push(' ');
+                render_markdown(children, out);
+                out.push('\n');
+            }
+            Node::HorizontalRule => {
+                ensure_line_start(out);
+                out.push_str("---\n");
+            }
+        }
+    }
+}
+
+fn render_plain_text(nodes: &[Node], out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Strong(children)
+            | Node::Emphasis(children)
+            | Node::Underline(children)
+            | Node::Strikethrough(children) => render_plain_text(children, out),
+            Node::Code(code) => out.push_str(code),
+            Node::Link {
+                href,
+                gid,
+                children,
+            } => {
+                let before = out.len();
+                render_plain_text(children, out);
+                if out.len() == before {
+                    if let Some(text) = href.as_ref().or(gid.as_ref()) {
+                        out.push_str(text);
+                    }
+                }
+            }
+            Node::List { ordered, items } => {
+                ensure_line_start(out);
+                for (index, item) in items.iter().enumerate() {
+                    let marker = if *ordered {
+                        format!("{}. ", index + 1)
+                    } else {
+                        String::from("- ")
+                    };
+                    let mut content = String::new();
+                    render_plain_text(item, &mut content);
+                    if content.is_empty() {
+                        content.push('\n');
+                    }
+                    out.push_str(&indent_lines(&content, &marker, &" ".repeat(marker.len())));
+                }
+            }
+            Node::CodeBlock(code) => {
+                ensure_line_start(out);
+                out.push_str(code);
+                ensure_line_start(out);
+            }
+            Node::Blockquote(children) | Node::Heading { children, .. } => {
+                ensure_line_start(out);
+                render_plain_text(children, out);
+                ensure_line_start(out);
+            }
+            Node::HorizontalRule => ensure_line_start(out),
+        }
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn render_rich_text(nodes: &[Node], out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(&escape_xml(text)),
+            Node::Strong(children) => wrap_rich_text("strong", children, out),
+            Node::Emphasis(children) => wrap_rich_text("em", children, out),
+            Node::Underline(children) => wrap_rich_text("u", children, out),
+            Node::Strikethrough(children) => wrap_rich_text("s", children, out),
+            Node::Code(code) => out.push_str(&format!("<code>{}</code>", escape_xml(code))),
+            Node::Link {
+                href,
+                gid,
+                children,
+            } => {
+                out.push_str("<a");
+                if let Some(href) = href {
+                    out.push_str(&format!(" href=\"{}\"", escape_xml(href)));
+                }
+                if let Some(gid) = gid {
+                    out.push_str(&format!(" data-asana-gid=\"{}\"", escape_xml(gid)));
+                }
+                if children.is_empty() {
+                    out.push_str("/>");
+                } else {
+                    out.push('>');
+                    render_rich_text(children, out);
+                    out.push_str("</a>");
+                }
+            }
+            Node::List { ordered, items } => {
+                let tag = if *ordered { "ol" } else { "ul" };
+                out.push_str(&format!("<{}>", tag));
+                for item in items {
+                    wrap_rich_text("li", item, out);
+                }
+                out.push_str(&format!("</{}>", tag));
+            }
+            Node::CodeBlock(code) => out.push_str(&format!("<pre>{}</pre>", escape_xml(code))),
+            Node::Blockquote(children) => wrap_rich_text("blockquote", children, out),
+            // Asana only supports two heading levels
+            Node::Heading { level, children } => {
+                let tag = if *level <= 1 { "h1" } else { "h2" };
+                wrap_rich_text(tag, children, out);
+            }
+            Node::HorizontalRule => out.push_str("<hr/>"),
+        }
+    }
+}
+
+fn wrap_rich_text(tag: &str, children: &[Node], out: &mut String) {
+    out.push_str(&format!("<{}>", tag));
+    render_rich_text(children, out);
+    out.push_str(&format!("</{}>", tag));
+}
+
+// Markdown parsing
+
+fn parse_blocks(lines: &[&str]) -> Vec<Node> {
+    let mut nodes = Vec::new();
+    let mut index = 0;
+
+    while index < lines.len() {
+        let line = lines[index];
+        let trimmed = line.trim_start();
+
+        if trimmed.starts_with("```") {
+            let mut code = String::new();
+            index += 1;
+            while index < lines.len() && !lines[index].trim_start().starts_with("```") {
+                code.push_str(lines[index]);
+                code.push('\n');
+                index += 1;
+            }
+            index += 1;
+            nodes.push(Node::CodeBlock(code));
+        } else if is_horizontal_rule(trimmed) {
+            nodes.push(Node::HorizontalRule);
+            index += 1;
+        } else if let Some((level, text)) = heading(trimmed) {
+            nodes.push(Node::Heading {
+                level,
+                children: parse_inline(text),
+            });
+            index += 1;
+        } else if trimmed.starts_with('>') {
+            let mut quoted = Vec::new();
+            while index < lines.len() {
+                let Some(rest) = lines[index].trim_start().strip_prefix('>') else {
+                    break;
+                };
+                quoted.push(rest.strip_prefix(' ').unwrap_or(rest));
+                index += 1;
+            }
+            let mut children = parse_blocks(&quoted);
+            if let Some(Node::Text(text)) = children.last_mut() {
+                if text.ends_with('\n') {
+                    text.pop();
+                }
+            }
+            nodes.push(Node::Blockquote(children));
+        } else if list_marker(line).is_some() {
+            nodes.push(parse_list(lines, &mut index));
+        } else {
+            for node in parse_inline(line) {
+                match node {
+                    Node::Text(text) => push_text(&mut nodes, &text),
+                    node => nodes.push(node),
+                }
+            }
+            push_text(&mut nodes, "\n");
+            index += 1;
+        }
+    }
+
+    nodes
+}
+
+fn is_horizontal_rule(line: &str) -> bool {
+    let line = line.trim_end();
+    ["---", "***", "___"]
+        .iter()
+        .any(|rule| line.len() >= 3 && line.chars().all(|c| rule.starts_with(c)))
+}
+
+fn heading(line: &str) -> Option<(u8, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    line[level..]
+        .strip_prefix(' ')
+        .map(|text| (level as u8, text.trim()))
+}
+
+/// Returns the indentation, whether the item is ordered, and the item text.
+fn list_marker(line: &str) -> Option<(usize, bool, &str)> {
+    let trimmed = line.trim_start();
+    let indent = line.len() - trimmed.len();
+
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(text) = trimmed.strip_prefix(bullet) {
+            return Some((indent, false, text));
+        }
+    }
+
+    let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        if let Some(text) = trimmed[digits..].strip_prefix(". ") {
+            return Some((indent, true, text));
+        }
+    }
+
+    None
+}
+
+fn parse_list(lines: &[&str], index: &mut usize) -> Node {
+    let (indent, ordered, _) = list_marker(lines[*index]).unwrap_or((0, false, ""));
+    let mut items = Vec::new();
+
+    while *index < lines.len() {
+        match list_marker(lines[*index]) {
+            Some((item_indent, item_ordered, text))
+                if item_indent == indent && item_ordered == ordered =>
+            {
+                *index += 1;
+                let mut item = parse_inline(text);
+                while *index < lines.len() {
+                    match list_marker(lines[*index]) {
+                        Some((nested_indent, _, _)) if nested_indent > indent => {
+                            item.push(parse_list(lines, index));
+                        }
+                        _ => break,
+                    }
+                }
+                items.push(item);
+            }
+            _ => break,
+        }
+    }
+
+    Node::List { ordered, items }
+}
+
+fn find_closing(chars: &[char], start: usize, delimiter: &[char]) -> Option<usize> {
+    let mut index = start;
+    while index + delimiter.len() <= chars.len() {
+        if chars[index] == '\\' {
+            index += 2;
+            continue;
+        }
+        if chars[index..index + delimiter.len()] == *delimiter {
+            return Some(index);
+        }
+        index += 1;
+    }
+
+    None
+}
+
+/// Split a `href "asana:gid"` link target into the href and the mention gid.
+fn link_title_gid(target: &str) -> Option<(&str, &str)> {
+    let (href, title) = target.rsplit_once(" \"")?;
+    let gid = title.strip_suffix('"')?.strip_prefix(ASANA_SCHEME)?;
+
+    Some((href, gid))
+}
+
+fn parse_inline(text: &str) -> Vec<Node> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut nodes = Vec::new();
+    let mut buffer = String::new();
+    let mut index = 0;
+
+    let flush = |buffer: &mut String, nodes: &mut Vec<Node>| {
+        push_text(nodes, buffer);
+        buffer.clear();
+    };
+
+    while index < chars.len() {
+        let c = chars[index];
+        let rest = &chars[index..];
+
+        if c == '\\' && index + 1 < chars.len() {
+            buffer.push(chars[index + 1]);
+            index += 2;
+            continue;
+        }
+
+        let wrapped = [
+            (&['*', '*'][..], 0u8),
+            (&['~', '~'][..], 1),
+            (&['*'][..], 2),
+            (&['_'][..], 2),
+        ]
+        .iter()
+        .find(|(delimiter, _)| rest.starts_with(delimiter))
+        .and_then(|(delimiter, kind)| {
+            let end = find_closing(&chars, index + delimiter.len(), delimiter)?;
+            (end > index + delimiter.len()).then_some((delimiter.len(), end, *kind))
+        });
+        if let Some((width, end, kind)) = wrapped {
+            flush(&mut buffer, &mut nodes);
+            let inner: String = chars[index + width..end].iter().collect();
+            let children = parse_inline(&inner);
+            nodes.push(match kind {
+                0 => Node::Strong(children),
+                1 => Node::Strikethrough(children),
+                _ => Node::Emphasis(children),
+            });
+            index = end + width;
+            continue;
+        }
+
+        if c == '`' {
+            let width = rest.iter().take_while(|c| **c == '`').count();
+            let delimiter = vec!['`'; width];
+            if let Some(end) = find_closing(&chars, index + width, &delimiter) {
+                flush(&mut buffer, &mut nodes);
+                let code: String = chars[index + width..end].iter().collect();
+                let code = code.strip_prefix(' ').unwrap_or(&code);
+                let code = code.strip_suffix(' ').unwrap_or(code);
+                nodes.push(Node::Code(code.to_string()));
+                index = end + width;
+                continue;
+            }
+        }
+
+        if c == '[' {
+            let link = find_closing(&chars, index + 1, &[']', '(']).and_then(|text_end| {
+                let href_end = find_closing(&chars, text_end + 2, &[')'])?;
+                Some((text_end, href_end))
+            });
+            if let Some((text_end, href_end)) = link {
+                flush(&mut buffer, &mut nodes);
+                let text: String = chars[index + 1..text_end].iter().collect();
+                let target: String = chars[text_end + 2..href_end].iter().collect();
+                let (href, gid) = match target.strip_prefix(ASANA_SCHEME) {
+                    Some(gid) => (None, Some(gid.to_string())),
+                    None => match link_title_gid(&target) {
+                        Some((href, gid)) => (Some(href.to_string()), Some(gid.to_string())),
+                        None => (Some(target.clone()), None),
+                    },
+                };
+                // A mention rendered without a label round-trips as a self-closing `<a/>`
+                let children = match &gid {
+                    Some(gid) if *gid == text => Vec::new(),
+                    _ => parse_inline(&text),
+                };
+                nodes.push(Node::Link {
+                    href,
+                    gid,
+                    children,
+                });
+                index = href_end + 1;
+                continue;
+            }
+        }
+
+        buffer.push(c);
+        index += 1;
+    }
+    flush(&mut buffer, &mut nodes);
+
+    nodes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(rich_text: &str) -> String {
+        let markdown = rich_text_to_markdown(rich_text).unwrap();
+        markdown_to_rich_text(&markdown)
+    }
+
+    #[test]
+    fn mention_round_trips() {
+        let rich_text = r#"<body>Ping <a data-asana-gid="123"/> now</body>"#;
+        assert_eq!(
+            rich_text_to_markdown(rich_text).unwrap(),
+            "Ping [123](asana:123) now"
+        );
+        assert_eq!(round_trip(rich_text), rich_text);
+    }
+
+    #[test]
+    fn mention_with_href_keeps_gid() {
+        let rich_text =
+            r#"<body><a href="https://app.asana.com/0/0/123" data-asana-gid="123">Task</a></body>"#;
+        assert_eq!(
+            rich_text_to_markdown(rich_text).unwrap(),
+            r#"[Task](https://app.asana.com/0/0/123 "asana:123")"#
+        );
+        assert_eq!(round_trip(rich_text), rich_text);
+    }
+
+    #[test]
+    fn plain_link_round_trips() {
+        let rich_text = r#"<body><a href="https://example.com">site</a></body>"#;
+        assert_eq!(round_trip(rich_text), rich_text);
+    }
+
+    #[test]
+    fn line_leading_block_markers_are_escaped() {
+        let rich_text =
+            "<body># not a heading\n- not a list\n&gt; not a quote\n1. not ordered</body>";
+        let markdown = rich_text_to_markdown(rich_text).unwrap();
+        assert_eq!(
+            markdown,
+            "\\# not a heading\n\\- not a list\n\\> not a quote\n1\\. not ordered"
+        );
+        assert_eq!(markdown_to_rich_text(&markdown), rich_text);
+    }
+
+    #[test]
+    fn markers_mid_line_are_not_escaped() {
+        let markdown = rich_text_to_markdown("<body>a - b # c</body>").unwrap();
+        assert_eq!(markdown, "a - b # c");
+    }
+
+    #[test]
+    fn block_structure_round_trips() {
+        let rich_text = "<body><h1>Title</h1><ul><li>one</li><li><strong>two</strong></li></ul>\
+            <ol><li>first</li></ol><blockquote>quoted</blockquote><hr/></body>";
+        assert_eq!(round_trip(rich_text), rich_text);
+    }
+
+    #[test]
+    fn accepts_non_self_closing_hr() {
+        assert_eq!(
+            parse("<body>a<hr>b</body>").unwrap(),
+            parse("<body>a<hr/>b</body>").unwrap()
+        );
+        assert_eq!(
+            parse("<body><hr></hr></body>").unwrap(),
+            vec![Node::HorizontalRule]
+        );
+    }
+}
@@ -1,200 +1,967 @@
-use serde::{Deserialize, Serialize};
+use super::dates::{Date, DateTime};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Declares an enum for one of Asana's string-typed fields. Values this crate does not know are
+/// kept in `Unknown` rather than failing to parse, and serialize back unchanged.
+macro_rules! string_enum {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident => $value:literal,)+ }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($variant,)+
+            Unknown(String),
+        }
+
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $value,)+
+                    $name::Unknown(value) => value,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($value => $name::$variant,)+
+                    other => $name::Unknown(other.to_string()),
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+                Ok($name::from(value.as_str()))
+            }
+        }
+    };
+}
+
+string_enum! {
+    /// Palette colour of projects, portfolios, tags and enum options.
+    Color {
+        DarkPink => "dark-pink",
+        DarkGreen => "dark-green",
+        DarkBlue => "dark-blue",
+        DarkRed => "dark-red",
+        DarkTeal => "dark-teal",
+        DarkBrown => "dark-brown",
+        DarkOrange => "dark-orange",
+        DarkPurple => "dark-purple",
+        DarkWarmGray => "dark-warm-gray",
+        LightPink => "light-pink",
+        LightGreen => "light-green",
+        LightBlue => "light-blue",
+        LightRed => "light-red",
+        LightTeal => "light-teal",
+        LightBrown => "light-brown",
+        LightOrange => "light-orange",
+        LightPurple => "light-purple",
+        LightWarmGray => "light-warm-gray",
+        None => "none",
+    }
+}
+
+string_enum! {
+    DefaultView {
+        List => "list",
+        Board => "board",
+        Calendar => "calendar",
+        Timeline => "timeline",
+    }
+}
+
+string_enum! {
+    PrivacySetting {
+        PublicToWorkspace => "public_to_workspace",
+        PrivateToTeam => "private_to_team",
+        Private => "private",
+    }
+}
+
+string_enum! {
+    /// Team visibility.
+    Visibility {
+        Secret => "secret",
+        RequestToJoin => "request_to_join",
+        Public => "public",
+    }
+}
+
+string_enum! {
+    /// Who may perform a team management action.
+    TeamAccessLevel {
+        AllTeamMembers => "all_team_members",
+        OnlyTeamAdmins => "only_team_admins",
+        NoRestriction => "no_restriction",
+    }
+}
+
+string_enum! {
+    /// Access level granted to a member of a project.
+    AccessLevel {
+        Admin => "admin",
+        Editor => "editor",
+        Commenter => "commenter",
+        Viewer => "viewer",
+    }
+}
+
+string_enum! {
+    /// Subtype of a resource; the known values span tasks, custom fields, status updates,
+    /// goals and jobs.
+    ResourceSubtype {
+        DefaultTask => "default_task",
+        Milestone => "milestone",
+        Section => "section",
+        Approval => "approval",
+        Text => "text",
+        Enum => "enum",
+        MultiEnum => "multi_enum",
+        Number => "number",
+        Date => "date",
+        People => "people",
+        Formula => "formula",
+        Percentage => "percentage",
+        Currency => "currency",
+        ProjectStatusUpdate => "project_status_update",
+        PortfolioStatusUpdate => "portfolio_status_update",
+        GoalStatusUpdate => "goal_status_update",
+        Subgoal => "subgoal",
+        SupportingWork => "supporting_work",
+        DuplicateProject => "duplicate_project",
+        DuplicateTask => "duplicate_task",
+        ExportProject => "export_project",
+        InstantiateProject => "instantiate_project",
+    }
+}
+
+string_enum! {
+    JobStatus {
+        NotStarted => "not_started",
+        InProgress => "in_progress",
+        Succeeded => "succeeded",
+        Failed => "failed",
+    }
+}
+
+string_enum! {
+    /// Overall status reported by a status update on a project, portfolio or goal.
+    StatusType {
+        OnTrack => "on_track",
+        AtRisk => "at_risk",
+        OffTrack => "off_track",
+        OnHold => "on_hold",
+        Complete => "complete",
+        Achieved => "achieved",
+        Partial => "partial",
+        Missed => "missed",
+        Dropped => "dropped",
+    }
+}
+
+string_enum! {
+    /// Colour Asana renders for a status.
+    StatusColor {
+        Green => "green",
+        Yellow => "yellow",
+        Red => "red",
+        Blue => "blue",
+        Complete => "complete",
+    }
+}
+
+impl StatusType {
+    pub fn color(&self) -> StatusColor {
+        match self {
+            StatusType::OnTrack | StatusType::Achieved => StatusColor::Green,
+            StatusType::AtRisk | StatusType::Partial => StatusColor::Yellow,
+            StatusType::OffTrack | StatusType::Missed | StatusType::Dropped => StatusColor::Red,
+            StatusType::OnHold => StatusColor::Blue,
+            StatusType::Complete => StatusColor::Complete,
+            StatusType::Unknown(value) => StatusColor::Unknown(value.clone()),
+        }
+    }
+}
+
+/// Fields Asana returned that the model does not (yet) know about. Flattened back into the
+/// object on serialize so replicated records keep them.
+pub type Extra = serde_json::Map<String, serde_json::Value>;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ProjectResponseData {
     pub gid: String,
-    resource_type: String,
-    name: String,
-    archived: bool,
-    color: Option<String>,
-    created_at: String,
-    current_status: Option<CurrentStatus>,
-    current_status_update: Option<CurrentStatusUpdate>,
-    custom_field_settings: Option<Vec<CustomFieldSetting>>,
-    default_view: String,
-    due_date: Option<String>,
-    due_on: Option<String>,
-    html_notes: Option<String>,
-    members: Vec<Member>,
-    modified_at: String,
-    notes: Option<String>,
-    privacy_setting: Option<String>,
-    start_on: Option<String>,
-    default_access_level: Option<String>,
-    minimum_access_level_for_customization: Option<String>,
-    minimum_access_level_for_sharing: Option<String>,
-    custom_fields: Option<Vec<CustomField>>,
-    completed: bool,
-    completed_at: Option<String>,
-    completed_by: Option<Member>,
-    followers: Vec<Member>,
-    owner: Member,
-    team: Option<Team>,
-    icon: Option<String>,
-    permalink_url: Option<String>,
-    project_brief: Option<ProjectBrief>,
-    created_from_template: Option<ProjectTemplate>,
-    workspace: Workspace,
+    pub resource_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
+    pub color: Option<Color>,
+    pub created_at: Option<DateTime>,
+    pub current_status: Option<CurrentStatus>,
+    pub current_status_update: Option<CurrentStatusUpdate>,
+    pub custom_field_settings: Option<Vec<CustomFieldSetting>>,
+    pub default_view: Option<DefaultView>,
+    pub due_date: Option<Date>,
+    pub due_on: Option<Date>,
+    pub html_notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub members: Option<Vec<Member>>,
+    pub modified_at: Option<DateTime>,
+    pub notes: Option<String>,
+    pub privacy_setting: Option<PrivacySetting>,
+    pub start_on: Option<Date>,
+    pub default_access_level: Option<AccessLevel>,
+    pub minimum_access_level_for_customization: Option<AccessLevel>,
+    pub minimum_access_level_for_sharing: Option<AccessLevel>,
+    pub custom_fields: Option<Vec<CustomField>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed: Option<bool>,
+    pub completed_at: Option<DateTime>,
+    pub completed_by: Option<Member>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub followers: Option<Vec<Member>>,
+    pub owner: Option<Member>,
+    pub team: Option<Team>,
+    pub icon: Option<String>,
+    pub permalink_url: Option<String>,
+    pub project_brief: Option<ProjectBrief>,
+    pub created_from_template: Option<ProjectTemplate>,
+    pub workspace: Option<Workspace>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-struct CurrentStatus {
-    gid: String,
-    resource_type: String,
-    title: String,
-    text: String,
-    html_text: String,
-    color: String,
-    author: Member,
-    created_at: String,
-    created_by: Member,
-    modified_at: String,
+pub struct CurrentStatus {
+    pub gid: String,
+    pub resource_type: String,
+    pub title: Option<String>,
+    pub text: Option<String>,
+    pub html_text: Option<String>,
+    pub color: Option<StatusColor>,
+    pub author: Option<Member>,
+    pub created_at: Option<DateTime>,
+    pub created_by: Option<Member>,
+    pub modified_at: Option<DateTime>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-struct CurrentStatusUpdate {
-    gid: String,
-    resource_type: String,
-    title: String,
-    resource_subtype: String,
+pub struct CurrentStatusUpdate {
+    pub gid: String,
+    pub resource_type: String,
+    pub title: Option<String>,
+    pub resource_subtype: Option<ResourceSubtype>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-struct CustomFieldSetting {
-    gid: String,
-    resource_type: String,
-    project: Project,
-    is_important: bool,
-    parent: Project,
-    custom_field: CustomField,
+pub struct CustomFieldSetting {
+    pub gid: String,
+    pub resource_type: String,
+    pub project: Option<Project>,
+    #[serde(default)]
+    pub is_important: bool,
+    pub parent: Option<Project>,
+    pub custom_field: Option<CustomField>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-struct Project {
-    gid: String,
-    resource_type: String,
-    name: String,
+pub struct Project {
+    pub gid: String,
+    pub resource_type: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-struct CustomField {
-    gid: String,
-    resource_type: String,
-    name: String,
-    resource_subtype: String,
-    r#type: String,
-    enum_options: Option<Vec<EnumOption>>,
-    enabled: bool,
-    representation_type: Option<String>,
-    id_prefix: Option<String>,
-    is_formula_field: bool,
-    date_value: Option<DateValue>,
-    enum_value: Option<EnumOption>,
-    multi_enum_values: Option<Vec<EnumOption>>,
-    number_value: Option<f64>,
-    text_value: Option<String>,
-    display_value: Option<String>,
-    description: Option<String>,
-    precision: Option<u32>,
-    format: Option<String>,
-    currency_code: Option<String>,
-    custom_label: Option<String>,
-    custom_label_position: Option<String>,
-    is_global_to_workspace: Option<bool>,
-    has_notifications_enabled: Option<bool>,
-    asana_created_field: Option<String>,
-    is_value_read_only: Option<bool>,
-    created_by: Option<Member>,
-    people_value: Option<Vec<Member>>,
+pub struct CustomField {
+    pub gid: String,
+    pub resource_type: String,
+    #[serde(default)]
+    pub name: String,
+    pub resource_subtype: Option<ResourceSubtype>,
+    pub r#type: Option<ResourceSubtype>,
+    pub enum_options: Option<Vec<EnumOption>>,
+    #[serde(default)]
+    pub enabled: bool,
+    pub representation_type: Option<String>,
+    pub id_prefix: Option<String>,
+    #[serde(default)]
+    pub is_formula_field: bool,
+    pub date_value: Option<DateValue>,
+    pub enum_value: Option<EnumOption>,
+    pub multi_enum_values: Option<Vec<EnumOption>>,
+    pub number_value: Option<f64>,
+    pub text_value: Option<String>,
+    pub display_value: Option<String>,
+    pub description: Option<String>,
+    pub precision: Option<u32>,
+    pub format: Option<String>,
+    pub currency_code: Option<String>,
+    pub custom_label: Option<String>,
+    pub custom_label_position: Option<String>,
+    pub is_global_to_workspace: Option<bool>,
+    pub has_notifications_enabled: Option<bool>,
+    pub asana_created_field: Option<String>,
+    pub is_value_read_only: Option<bool>,
+    pub created_by: Option<Member>,
+    pub people_value: Option<Vec<Member>>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-struct EnumOption {
-    gid: String,
-    resource_type: String,
-    name: String,
-    enabled: bool,
-    color: Option<String>,
+pub struct EnumOption {
+    pub gid: String,
+    pub resource_type: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub enabled: bool,
+    pub color: Option<Color>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-struct DateValue {
-    date: Option<String>,
-    date_time: Option<String>,
+pub struct DateValue {
+    pub date: Option<Date>,
+    pub date_time: Option<DateTime>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-struct Member {
-    gid: String,
-    resource_type: String,
-    name: String,
+pub struct Member {
+    pub gid: String,
+    pub resource_type: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-struct Team {
-    gid: String,
-    resource_type: String,
-    name: String,
+pub struct Team {
+    pub gid: String,
+    pub resource_type: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-struct ProjectBrief {
-    gid: String,
-    resource_type: String,
+pub struct ProjectBrief {
+    pub gid: String,
+    pub resource_type: String,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-struct ProjectTemplate {
-    gid: String,
-    resource_type: String,
-    name: String,
+pub struct ProjectTemplate {
+    pub gid: String,
+    pub resource_type: String,
+    #[serde(default)]
+    pub name: String,
+    pub description: Option<String>,
+    pub html_description: Option<String>,
+    pub public: Option<bool>,
+    pub owner: Option<Member>,
+    pub team: Option<Team>,
+    pub color: Option<Color>,
+    pub requested_dates: Option<Vec<TemplateVariable>>,
+    pub requested_roles: Option<Vec<TemplateVariable>>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
+/// A date or role placeholder that must be supplied when instantiating a template.
 #[derive(Serialize, Deserialize, Debug)]
-struct Workspace {
-    gid: String,
-    resource_type: String,
-    name: String,
+pub struct TemplateVariable {
+    pub gid: String,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Workspace {
+    pub gid: String,
+    pub resource_type: String,
+    #[serde(default)]
+    pub name: String,
+    /// Only present when requested through `opt_fields`, as in `methods::get_workspaces`.
+    pub is_organization: Option<bool>,
+    pub email_domains: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
 // Webhook
 #[derive(Debug, Serialize, Deserialize)]
-pub struct Webhook {
-    pub data: WebhookData,
+pub struct WebhookData {
+    pub gid: String,
+    pub resource_type: String,
+    #[serde(default)]
+    pub active: bool,
+    pub resource: Option<Resource>,
+    #[serde(default)]
+    pub target: String,
+    pub created_at: Option<DateTime>,
+    pub last_failure_at: Option<DateTime>,
+    pub last_failure_content: Option<String>,
+    pub last_success_at: Option<DateTime>,
+    #[serde(default)]
+    pub delivery_retry_count: u32,
+    pub next_attempt_after: Option<DateTime>,
+    pub failure_deletion_timestamp: Option<DateTime>,
+    #[serde(default)]
+    pub filters: Vec<Filter>,
+    #[serde(default)]
+    pub is_workspace_webhook: bool,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Resource {
+    pub gid: String,
+    pub resource_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_subtype: Option<ResourceSubtype>,
+    #[serde(default)]
+    pub name: String,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
 
-    #[serde(rename = "X-Hook-Secret")]
-    pub x_hook_secret: String,
+/// Restricts which events a webhook delivers. Workspace-level webhooks require at least one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Filter {
+    pub resource_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_subtype: Option<ResourceSubtype>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
+string_enum! {
+    /// What happened to the resource, or to one of its fields, in a [`WebhookEvent`].
+    EventAction {
+        Changed => "changed",
+        Added => "added",
+        Removed => "removed",
+        Deleted => "deleted",
+        Undeleted => "undeleted",
+    }
+}
+
+/// Body of a webhook delivery. Heartbeats are delivered with no events.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEvents {
+    #[serde(default)]
+    pub events: Vec<WebhookEvent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    pub action: EventAction,
+    pub resource: Resource,
+    /// The resource `resource` was added to or removed from, e.g. the project of a task.
+    pub parent: Option<Resource>,
+    /// Who made the change; absent for changes made by Asana itself.
+    pub user: Option<Resource>,
+    pub created_at: DateTime,
+    /// Only present on `changed` events.
+    pub change: Option<EventChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventChange {
+    pub field: String,
+    pub action: EventAction,
+    /// Set for `changed`; a compact resource or a scalar depending on `field`.
+    pub new_value: Option<serde_json::Value>,
+    pub added_value: Option<serde_json::Value>,
+    pub removed_value: Option<serde_json::Value>,
+}
+
+// Pagination
+/// Envelope returned by Asana's paginated list endpoints.
 #[derive(Debug, Serialize, Deserialize)]
-pub struct WebhookData {
+pub struct ListResponse<T> {
+    pub data: Vec<T>,
+    pub next_page: Option<NextPage>,
+}
+
+/// Cursor for fetching the next page of a list response.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NextPage {
+    pub offset: String,
+    pub path: String,
+    pub uri: String,
+}
+
+/// Envelope returned by endpoints that respond with an empty `data` object, such as deletes.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EmptyResponse {
+    pub data: serde_json::Value,
+}
+
+// Tag
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Tag {
+    pub gid: String,
+    pub resource_type: String,
+    pub name: String,
+    pub color: Option<Color>,
+    pub notes: Option<String>,
+    pub created_at: Option<DateTime>,
+    pub followers: Option<Vec<Member>>,
+    pub workspace: Option<Workspace>,
+    pub permalink_url: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+// User
+#[derive(Debug, Serialize, Deserialize)]
+pub struct User {
+    pub gid: String,
+    pub resource_type: String,
+    pub name: String,
+    pub email: Option<String>,
+    pub photo: Option<UserPhoto>,
+    pub workspaces: Option<Vec<Workspace>>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UserPhoto {
+    pub image_21x21: Option<String>,
+    pub image_27x27: Option<String>,
+    pub image_36x36: Option<String>,
+    pub image_60x60: Option<String>,
+    pub image_128x128: Option<String>,
+    pub image_1024x1024: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+// Memberships
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TeamMembership {
     pub gid: String,
+    pub resource_type: String,
+    pub user: Member,
+    pub team: Team,
+    pub is_guest: Option<bool>,
+    pub is_limited_access: Option<bool>,
+    pub is_admin: Option<bool>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Membership {
+    pub gid: String,
+    pub resource_type: String,
+    pub parent: Option<Project>,
+    pub member: Option<Member>,
+    pub access_level: Option<AccessLevel>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+// Portfolio
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Portfolio {
+    pub gid: String,
+    pub resource_type: String,
+    pub name: String,
+    pub color: Option<Color>,
+    pub created_at: Option<DateTime>,
+    pub created_by: Option<Member>,
+    pub owner: Option<Member>,
+    pub members: Option<Vec<Member>>,
+    pub workspace: Option<Workspace>,
+    pub public: Option<bool>,
+    pub due_on: Option<Date>,
+    pub start_on: Option<Date>,
+    pub permalink_url: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+// Goal
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Goal {
+    pub gid: String,
+    pub resource_type: String,
+    pub name: String,
+    pub notes: Option<String>,
+    pub html_notes: Option<String>,
+    pub due_on: Option<Date>,
+    pub start_on: Option<Date>,
+    pub status: Option<String>,
+    pub is_workspace_level: Option<bool>,
+    pub owner: Option<Member>,
+    pub team: Option<Team>,
+    pub workspace: Option<Workspace>,
+    pub time_period: Option<TimePeriod>,
+    pub metric: Option<GoalMetric>,
+    pub followers: Option<Vec<Member>>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GoalMetric {
+    pub gid: String,
+    pub resource_type: String,
+    pub resource_subtype: Option<ResourceSubtype>,
+    pub precision: Option<u32>,
+    pub unit: Option<String>,
+    pub currency_code: Option<String>,
+    pub initial_number_value: Option<f64>,
+    pub target_number_value: Option<f64>,
+    pub current_number_value: Option<f64>,
+    pub current_display_value: Option<String>,
+    pub progress_source: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TimePeriod {
+    pub gid: String,
+    pub resource_type: String,
+    pub display_name: Option<String>,
+    pub period: Option<String>,
+    pub start_on: Option<Date>,
+    pub end_on: Option<Date>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GoalRelationship {
+    pub gid: String,
+    pub resource_type: String,
+    pub resource_subtype: Option<ResourceSubtype>,
+    pub supporting_resource: Option<Resource>,
+    pub supported_goal: Option<Resource>,
+    pub contribution_weight: Option<f64>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+// Job
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Job {
+    pub gid: String,
+    pub resource_type: String,
+    pub resource_subtype: Option<ResourceSubtype>,
+    pub status: JobStatus,
+    pub new_project: Option<Project>,
+    pub new_task: Option<Resource>,
+    pub new_project_template: Option<Resource>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+// Status update
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StatusUpdate {
+    pub gid: String,
+    pub resource_type: String,
+    pub resource_subtype: Option<ResourceSubtype>,
+    pub title: Option<String>,
+    pub text: Option<String>,
+    pub html_text: Option<String>,
+    pub status_type: Option<StatusType>,
+    pub author: Option<Member>,
+    pub created_at: Option<DateTime>,
+    pub created_by: Option<Member>,
+    pub modified_at: Option<DateTime>,
+    pub parent: Option<Resource>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+// Task
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Task {
+    pub gid: String,
+    pub resource_type: String,
+    pub name: String,
+    pub resource_subtype: Option<ResourceSubtype>,
+    pub notes: Option<String>,
+    pub completed: Option<bool>,
+    pub completed_at: Option<DateTime>,
+    pub assignee: Option<Member>,
+    pub due_on: Option<Date>,
+    pub due_at: Option<DateTime>,
+    pub start_on: Option<Date>,
+    pub created_at: Option<DateTime>,
+    pub modified_at: Option<DateTime>,
+    pub projects: Option<Vec<Project>>,
+    pub tags: Option<Vec<Tag>>,
+    pub permalink_url: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+// Typeahead
+/// Compact object returned by the typeahead endpoint, keyed by its `resource_type`. Types this
+/// crate does not model, such as teams, are kept in `Other` instead of failing the response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "RawTypeaheadResult", into = "RawTypeaheadResult")]
+pub enum TypeaheadResult {
+    User {
+        gid: String,
+        name: String,
+    },
+    Project {
+        gid: String,
+        name: String,
+    },
+    Tag {
+        gid: String,
+        name: String,
+    },
+    Task {
+        gid: String,
+        name: String,
+    },
+    Portfolio {
+        gid: String,
+        name: String,
+    },
+    Other {
+        resource_type: String,
+        gid: String,
+        name: String,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawTypeaheadResult {
     resource_type: String,
-    active: bool,
-    resource: Resource,
-    target: String,
-    created_at: String,
-    last_failure_at: Option<String>,
-    last_failure_content: String,
-    last_success_at: String,
-    delivery_retry_count: u32,
-    next_attempt_after: Option<String>,
-    failure_deletion_timestamp: Option<String>,
-    filters: Vec<Filter>,
-    is_workspace_webhook: bool,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct Resource {
     gid: String,
-    resource_type: String,
+    #[serde(default)]
     name: String,
 }
 
+impl From<RawTypeaheadResult> for TypeaheadResult {
+    fn from(raw: RawTypeaheadResult) -> Self {
+        let RawTypeaheadResult {
+            resource_type,
+            gid,
+            name,
+        } = raw;
+
+        match resource_type.as_str() {
+            "user" => TypeaheadResult::User { gid, name },
+            "project" => TypeaheadResult::Project { gid, name },
+            "tag" => TypeaheadResult::Tag { gid, name },
+            "task" => TypeaheadResult::Task { gid, name },
+            "portfolio" => TypeaheadResult::Portfolio { gid, name },
+            _ => TypeaheadResult::Other {
+                resource_type,
+                gid,
+                name,
+            },
+        }
+    }
+}
+
+impl From<TypeaheadResult> for RawTypeaheadResult {
+    fn from(result: TypeaheadResult) -> Self {
+        let resource_type = result.resource_type().to_string();
+        let (gid, name) = match result {
+            TypeaheadResult::User { gid, name }
+            | TypeaheadResult::Project { gid, name }
+            | TypeaheadResult::Tag { gid, name }
+            | TypeaheadResult::Task { gid, name }
+            | TypeaheadResult::Portfolio { gid, name }
+            | TypeaheadResult::Other { gid, name, .. } => (gid, name),
+        };
+
+        RawTypeaheadResult {
+            resource_type,
+            gid,
+            name,
+        }
+    }
+}
+
+impl TypeaheadResult {
+    pub fn resource_type(&self) -> &str {
+        match self {
+            TypeaheadResult::User { .. } => "user",
+            TypeaheadResult::Project { .. } => "project",
+            TypeaheadResult::Tag { .. } => "tag",
+            TypeaheadResult::Task { .. } => "task",
+            TypeaheadResult::Portfolio { .. } => "portfolio",
+            TypeaheadResult::Other { resource_type, .. } => resource_type,
+        }
+    }
+
+    pub fn gid(&self) -> &str {
+        match self {
+            TypeaheadResult::User { gid, .. }
+            | TypeaheadResult::Project { gid, .. }
+            | TypeaheadResult::Tag { gid, .. }
+            | TypeaheadResult::Task { gid, .. }
+            | TypeaheadResult::Portfolio { gid, .. }
+            | TypeaheadResult::Other { gid, .. } => gid,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            TypeaheadResult::User { name, .. }
+            | TypeaheadResult::Project { name, .. }
+            | TypeaheadResult::Tag { name, .. }
+            | TypeaheadResult::Task { name, .. }
+            | TypeaheadResult::Portfolio { name, .. }
+            | TypeaheadResult::Other { name, .. } => name,
+        }
+    }
+}
+
+// Time tracking
 #[derive(Debug, Serialize, Deserialize)]
-struct Filter {
-    resource_type: String,
-    resource_subtype: String,
-    action: String,
-    fields: Vec<String>,
+pub struct TimeTrackingEntry {
+    pub gid: String,
+    pub resource_type: String,
+    pub duration_minutes: u64,
+    pub entered_on: Date,
+    pub created_at: Option<DateTime>,
+    pub created_by: Option<Member>,
+    pub task: Option<Resource>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+impl TimeTrackingEntry {
+    pub fn duration(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.duration_minutes * 60)
+    }
+}
+
+// Audit log
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuditLogEvent {
+    pub gid: String,
+    pub created_at: DateTime,
+    pub event_type: String,
+    pub event_category: Option<String>,
+    pub actor: AuditLogActor,
+    pub resource: AuditLogResource,
+    pub context: AuditLogContext,
+    /// Event specific payload; its shape depends on `event_type`.
+    pub details: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuditLogActor {
+    pub actor_type: String,
+    pub gid: Option<String>,
+    pub name: Option<String>,
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuditLogResource {
+    pub resource_type: String,
+    pub resource_subtype: Option<ResourceSubtype>,
+    pub gid: Option<String>,
+    pub name: Option<String>,
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuditLogContext {
+    pub context_type: String,
+    pub api_authentication_method: Option<String>,
+    pub client_ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub oauth_app_name: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn project_round_trip_keeps_unknown_fields() {
+        let input = json!({
+            "gid": "1200",
+            "resource_type": "project",
+            "project_brief": { "gid": "1300", "resource_type": "project_brief", "title": "Brief" },
+            "created_from_template": {
+                "gid": "1400",
+                "resource_type": "project_template",
+                "name": "Launch",
+                "layout": "board"
+            },
+            "team": { "gid": "1500", "resource_type": "team", "name": "Ops", "visibility": "secret" },
+            "halfway_point": true
+        });
+
+        let project: ProjectResponseData = serde_json::from_value(input).unwrap();
+        let output = serde_json::to_value(&project).unwrap();
+
+        assert_eq!(output["project_brief"]["title"], "Brief");
+        assert_eq!(output["created_from_template"]["layout"], "board");
+        assert_eq!(output["team"]["visibility"], "secret");
+        assert_eq!(output["halfway_point"], true);
+        for field in ["name", "archived", "members", "completed", "followers"] {
+            assert!(output.get(field).is_none(), "{} was not requested", field);
+        }
+    }
+
+    #[test]
+    fn nested_models_round_trip_unknown_fields() {
+        let filter = json!({ "resource_type": "task", "action": "changed", "scope": "all" });
+        let output =
+            serde_json::to_value(serde_json::from_value::<Filter>(filter.clone()).unwrap());
+        assert_eq!(output.unwrap(), filter);
+
+        let photo = json!({ "image_21x21": "a.png", "image_256x256": "b.png" });
+        let output = serde_json::to_value(serde_json::from_value::<UserPhoto>(photo).unwrap());
+        assert_eq!(output.unwrap()["image_256x256"], "b.png");
+    }
 }
@@ -1,4 +1,8 @@
-use asana_replicator_public::prelude::{asana, CreateTeamResponse};
+use asana_replicator_public::{
+    prelude::{asana, CreateTeamResponse},
+    webhooks::secrets::{MongoSecretStore, WebhookSecrets},
+};
+use color_eyre::eyre::eyre;
 
 #[tokio::main]
 async fn main() -> color_eyre::Result<()> {
@@ -18,43 +22,49 @@ async fn main() -> color_eyre::Result<()> {
         .with_line_number(true)
         .init();
 
+    // The server answers the handshake, so it must share our secret store to accept it
+    let db_url = std::env::var("DATABASE_URL")
+        .map_err(|_| eyre!("DATABASE_URL must point at the server's database"))?;
+    let db_name = std::env::var("DATABASE_NAME").unwrap_or("asana_replicator".to_string());
+    let db = mongodb::Client::with_uri_str(db_url)
+        .await?
+        .database(&db_name);
+    let secrets = WebhookSecrets::new(MongoSecretStore::new(&db));
+
     let client = asana::Client::default();
     let (resp, _) = asana::methods::get_workspaces(&client).await?;
 
-    let mut details = resp.data.iter().filter_map(|workspace| {
-        let workspace_gid = workspace.get("gid")?;
-        let workspace_name = workspace.get("name")?;
-        tracing::info!("Wokspace: GID: {}, Name: {}", workspace_gid, workspace_name);
+    let mut details = resp.data.iter().filter(|workspace| {
+        tracing::info!(
+            "Workspace: GID: {}, Name: {}",
+            workspace.gid,
+            workspace.name
+        );
 
-        if workspace_name == "inertialbox.com" {
-            Some((workspace_gid, workspace_name))
-        } else {
-            None
-        }
+        workspace.name == "inertialbox.com"
     });
 
-    if let Some((workspace_gid, _)) = details.next() {
+    if let Some(workspace) = details.next() {
+        let workspace_gid = &workspace.gid;
         let (resp, _) =
-            asana::methods::create_team(&client, &workspace_gid, "rust-replicator-10").await?;
+            asana::methods::create_team(&client, workspace_gid, "rust-replicator-10").await?;
         let team_data: CreateTeamResponse = resp;
 
         let (project_resp, _) = asana::methods::create_project(
             &client,
-            &workspace_gid,
+            workspace_gid,
             &team_data.data.gid,
             "project-10",
         )
         .await?;
 
         let project_gid = project_resp.data.gid;
-        let (webhook_resp, _) = asana::methods::create_webhook(&client, &project_gid).await?;
-        dbg!(&webhook_resp);
+        let webhook_resp = secrets.create_webhook(&client, &project_gid, &[]).await?;
 
         tracing::info!(
-            "Webhook `gid: {}` created for Project `gid: {}` / X-Hook-Secret: {}",
+            "Webhook `gid: {}` created for Project `gid: {}`",
             webhook_resp.data.gid,
             &project_gid,
-            webhook_resp.x_hook_secret
         );
     }
 
@@ -1,27 +1,295 @@
-use crate::error::Error;
+use crate::{asana::types, error::Error};
+use axum::http::{HeaderName, HeaderValue, Method};
 use regex::Captures;
 use regex::Regex;
+use serde::Deserialize;
 
-use std::env;
+use std::{env, fmt, str::FromStr};
 
 #[allow(dead_code)]
-#[derive(Debug, Clone, Default)]
+#[derive(Clone, Default)]
 pub struct AppConfig {
     asana_base_url: String,
     db_url: String,
+    db_name: String,
+    admin_token: Option<String>,
+    webhooks: Option<WebhookConfig>,
+    cors: CorsConfig,
+    admin_cors: CorsConfig,
+}
+
+/// CORS policy for one group of routes. `None` lists mean any value is allowed.
+#[derive(Debug, Clone)]
+pub struct CorsConfig {
+    pub origins: Option<Vec<OriginPattern>>,
+    pub methods: Option<Vec<Method>>,
+    pub headers: Option<Vec<HeaderName>>,
+    pub allow_credentials: bool,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            origins: Some(vec![OriginPattern::Exact(
+                "http://localhost:9001".to_string(),
+            )]),
+            methods: None,
+            headers: None,
+            allow_credentials: false,
+        }
+    }
+}
+
+impl CorsConfig {
+    /// Read `{prefix}ALLOWED_ORIGINS`, `{prefix}ALLOWED_METHODS`, `{prefix}ALLOWED_HEADERS` and
+    /// `{prefix}ALLOW_CREDENTIALS`. The lists are comma separated, and `*` allows anything.
+    fn from_env(prefix: &str, fallback: &CorsConfig) -> Result<Self, Error> {
+        let var = |name: &str| env::var(format!("{}{}", prefix, name)).ok();
+
+        let cors = Self {
+            origins: match var("ALLOWED_ORIGINS") {
+                Some(value) => parse_list(&value)?,
+                None => fallback.origins.clone(),
+            },
+            methods: match var("ALLOWED_METHODS") {
+                Some(value) => parse_list(&value)?,
+                None => fallback.methods.clone(),
+            },
+            headers: match var("ALLOWED_HEADERS") {
+                Some(value) => parse_list(&value)?,
+                None => fallback.headers.clone(),
+            },
+            allow_credentials: match var("ALLOW_CREDENTIALS") {
+                Some(value) => value.parse().map_err(|_| {
+                    Error::new(format!(
+                        "{}ALLOW_CREDENTIALS must be `true` or `false`",
+                        prefix
+                    ))
+                })?,
+                None => fallback.allow_credentials,
+            },
+        };
+
+        // Browsers ignore wildcards on credentialed requests, and tower-http refuses them.
+        if cors.allow_credentials
+            && (cors.origins.is_none() || cors.methods.is_none() || cors.headers.is_none())
+        {
+            return Err(Error::new(format!(
+                "{}ALLOW_CREDENTIALS requires explicit origins, methods and headers",
+                prefix
+            )));
+        }
+
+        Ok(cors)
+    }
+}
+
+/// `None` for `*`, otherwise each comma separated item parsed as `T`.
+fn parse_list<T: FromStr>(value: &str) -> Result<Option<Vec<T>>, Error>
+where
+    T::Err: fmt::Display,
+{
+    if value.trim() == "*" {
+        return Ok(None);
+    }
+
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| {
+            item.parse()
+                .map_err(|err| Error::new(format!("Invalid CORS value `{}`: {}", item, err)))
+        })
+        .collect::<Result<_, _>>()
+        .map(Some)
+}
+
+/// An allowed origin: either exact, or `scheme://*.domain` matching any subdomain of `domain`
+/// at any depth, but not `domain` itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OriginPattern {
+    Exact(String),
+    WildcardSubdomain { scheme: String, domain: String },
+}
+
+impl OriginPattern {
+    pub fn matches(&self, origin: &HeaderValue) -> bool {
+        let Ok(origin) = origin.to_str() else {
+            return false;
+        };
+
+        match self {
+            OriginPattern::Exact(allowed) => origin.eq_ignore_ascii_case(allowed),
+            OriginPattern::WildcardSubdomain { scheme, domain } => {
+                let origin = origin.to_ascii_lowercase();
+                let Some(host) = origin
+                    .strip_prefix(scheme.as_str())
+                    .and_then(|rest| rest.strip_prefix("://"))
+                else {
+                    return false;
+                };
+
+                host.strip_suffix(domain.as_str())
+                    .and_then(|subdomain| subdomain.strip_suffix('.'))
+                    .is_some_and(|subdomain| !subdomain.is_empty())
+            }
+        }
+    }
+}
+
+impl FromStr for OriginPattern {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim_end_matches('/').to_ascii_lowercase();
+        let Some((scheme, host)) = s.split_once("://") else {
+            return Err(Error::new(format!("Origin `{}` is missing a scheme", s)));
+        };
+
+        match host.strip_prefix("*.") {
+            Some(domain) if !domain.is_empty() && !domain.contains('*') => {
+                Ok(OriginPattern::WildcardSubdomain {
+                    scheme: scheme.to_string(),
+                    domain: domain.to_string(),
+                })
+            }
+            _ if host.contains('*') => Err(Error::new(format!(
+                "Origin `{}` may only use `*` as its leftmost label",
+                s
+            ))),
+            _ => Ok(OriginPattern::Exact(s)),
+        }
+    }
+}
+
+impl fmt::Display for OriginPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OriginPattern::Exact(origin) => f.write_str(origin),
+            OriginPattern::WildcardSubdomain { scheme, domain } => {
+                write!(f, "{}://*.{}", scheme, domain)
+            }
+        }
+    }
+}
+
+/// The webhooks we want to exist, loaded from the JSON file at `WEBHOOKS_CONFIG`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookConfig {
+    /// Workspace the watched resources belong to; existing webhooks are listed from here.
+    pub workspace_gid: String,
+    #[serde(default)]
+    pub resources: Vec<WatchedResource>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WatchedResource {
+    pub gid: String,
+    #[serde(default)]
+    pub kind: ResourceKind,
+    /// Required for workspaces.
+    #[serde(default)]
+    pub filters: Vec<types::Filter>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceKind {
+    #[default]
+    Project,
+    Workspace,
+}
+
+#[cfg(test)]
+impl AppConfig {
+    /// Default config with `ADMIN_API_TOKEN` as given.
+    pub fn with_admin_token(admin_token: Option<&str>) -> Self {
+        Self {
+            admin_token: admin_token.map(str::to_string),
+            ..Default::default()
+        }
+    }
+}
+
+impl AppConfig {
+    /// MongoDB connection string; empty when `DATABASE_URL` is unset.
+    pub fn db_url(&self) -> &str {
+        &self.db_url
+    }
+
+    pub fn db_name(&self) -> &str {
+        &self.db_name
+    }
+
+    /// Bearer token for the admin API, which is disabled when `ADMIN_API_TOKEN` is unset.
+    pub fn admin_token(&self) -> Option<&str> {
+        self.admin_token.as_deref()
+    }
+
+    pub fn webhooks(&self) -> Option<&WebhookConfig> {
+        self.webhooks.as_ref()
+    }
+
+    /// CORS for the public routes, from the `CORS_*` variables.
+    pub fn cors(&self) -> &CorsConfig {
+        &self.cors
+    }
+
+    /// CORS for `/admin`, from the `ADMIN_CORS_*` variables, each defaulting to its `CORS_*`
+    /// counterpart.
+    pub fn admin_cors(&self) -> &CorsConfig {
+        &self.admin_cors
+    }
+}
+
+impl fmt::Debug for AppConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AppConfig")
+            .field("asana_base_url", &self.asana_base_url)
+            .field("db_url", &sanitize_db_url(&self.db_url).unwrap_or_default())
+            .field("db_name", &self.db_name)
+            .field(
+                "admin_token",
+                &self.admin_token.as_ref().map(|_| "<REDACTED>"),
+            )
+            .field("webhooks", &self.webhooks)
+            .field("cors", &self.cors)
+            .field("admin_cors", &self.admin_cors)
+            .finish()
+    }
 }
 
 pub async fn config() -> Result<AppConfig, Error> {
     let asana_base_url = env::var("ASANA_BASE_URL").expect("ASANA_BASE_URL is missing!");
+    let db_url = env::var("DATABASE_URL").unwrap_or_default();
+    let db_name = env::var("DATABASE_NAME").unwrap_or("asana_replicator".to_string());
+    let admin_token = env::var("ADMIN_API_TOKEN")
+        .ok()
+        .filter(|token| !token.is_empty());
+    let webhooks = match env::var("WEBHOOKS_CONFIG") {
+        Ok(path) => {
+            let contents = tokio::fs::read_to_string(&path).await?;
+            Some(serde_json::from_str(&contents)?)
+        }
+        Err(_) => None,
+    };
+    let cors = CorsConfig::from_env("CORS_", &CorsConfig::default())?;
+    let admin_cors = CorsConfig::from_env("ADMIN_CORS_", &cors)?;
 
     Ok(AppConfig {
         asana_base_url,
-        db_url: String::from(""),
+        db_url,
+        db_name,
+        admin_token,
+        webhooks,
+        cors,
+        admin_cors,
     })
 }
 
 pub fn sanitize_db_url(url: &str) -> Result<String, Error> {
-    let re = Regex::new(r"^(postgres://[a-zA-Z\d\-\S]+):([a-zA-Z\d\-\S]*)@")?;
+    let re = Regex::new(r"^([a-z+]+://[a-zA-Z\d\-\S]+):([a-zA-Z\d\-\S]*)@")?;
     let result = re
         .replace(url, |caps: &Captures<'_>| {
             format!("{}:<PASSWORD_REDACTED>@", &caps[1])
@@ -30,3 +298,125 @@ pub fn sanitize_db_url(url: &str) -> Result<String, Error> {
 
     Ok(result)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, origin: &str) -> bool {
+        pattern
+            .parse::<OriginPattern>()
+            .unwrap()
+            .matches(&HeaderValue::from_str(origin).unwrap())
+    }
+
+    #[test]
+    fn wildcard_matches_subdomains() {
+        assert!(matches("https://*.example.com", "https://app.example.com"));
+        assert!(matches("https://*.example.com", "https://a.b.example.com"));
+        assert!(matches("https://*.example.com", "HTTPS://App.Example.com"));
+    }
+
+    #[test]
+    fn wildcard_rejects_bare_domain() {
+        assert!(!matches("https://*.example.com", "https://example.com"));
+        assert!(!matches("https://*.example.com", "https://.example.com"));
+    }
+
+    #[test]
+    fn wildcard_rejects_suffix_attack() {
+        assert!(!matches("https://*.example.com", "https://evilexample.com"));
+        assert!(!matches(
+            "https://*.example.com",
+            "https://app.evilexample.com"
+        ));
+        assert!(!matches(
+            "https://*.example.com",
+            "https://example.com.evil.net"
+        ));
+    }
+
+    #[test]
+    fn wildcard_rejects_scheme_mismatch() {
+        assert!(!matches("https://*.example.com", "http://app.example.com"));
+        assert!(!matches("https://*.example.com", "app.example.com"));
+    }
+
+    #[test]
+    fn trailing_slash_is_ignored_in_patterns_only() {
+        assert!(matches(
+            "https://app.example.com/",
+            "https://app.example.com"
+        ));
+        assert!(matches("https://*.example.com/", "https://app.example.com"));
+        assert!(!matches(
+            "https://*.example.com",
+            "https://app.example.com/"
+        ));
+        assert!(!matches(
+            "https://*.example.com",
+            "https://app.example.com:8443"
+        ));
+    }
+
+    #[test]
+    fn rejects_misplaced_wildcards() {
+        assert!("https://app.*.com".parse::<OriginPattern>().is_err());
+        assert!("https://*".parse::<OriginPattern>().is_err());
+        assert!("*.example.com".parse::<OriginPattern>().is_err());
+    }
+
+    fn cors_from_env(prefix: &str, vars: &[(&str, &str)]) -> Result<CorsConfig, Error> {
+        for (name, value) in vars {
+            env::set_var(format!("{}{}", prefix, name), value);
+        }
+
+        CorsConfig::from_env(prefix, &CorsConfig::default())
+    }
+
+    #[test]
+    fn rejects_credentials_with_wildcard() {
+        for (prefix, wildcard) in [
+            ("TEST_CORS_ORIGINS_", "ALLOWED_ORIGINS"),
+            ("TEST_CORS_METHODS_", "ALLOWED_METHODS"),
+            ("TEST_CORS_HEADERS_", "ALLOWED_HEADERS"),
+        ] {
+            let mut vars = vec![
+                ("ALLOWED_ORIGINS", "https://*.example.com"),
+                ("ALLOWED_METHODS", "GET,POST"),
+                ("ALLOWED_HEADERS", "content-type"),
+                ("ALLOW_CREDENTIALS", "true"),
+            ];
+            vars.retain(|(name, _)| *name != wildcard);
+            vars.push((wildcard, "*"));
+
+            assert!(cors_from_env(prefix, &vars).is_err(), "{} = *", wildcard);
+        }
+    }
+
+    #[test]
+    fn accepts_credentials_with_explicit_lists() {
+        let cors = cors_from_env(
+            "TEST_CORS_EXPLICIT_",
+            &[
+                (
+                    "ALLOWED_ORIGINS",
+                    "https://*.example.com, https://app.test/",
+                ),
+                ("ALLOWED_METHODS", "GET,POST"),
+                ("ALLOWED_HEADERS", "content-type"),
+                ("ALLOW_CREDENTIALS", "true"),
+            ],
+        )
+        .unwrap();
+
+        assert!(cors.allow_credentials);
+        let origins: Vec<String> = cors
+            .origins
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(origins, ["https://*.example.com", "https://app.test"]);
+    }
+}
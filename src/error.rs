@@ -5,7 +5,7 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
-use hyper::header::ToStrError;
+use hyper::{header::ToStrError, StatusCode};
 use serde_json::json;
 use std::error::Error as StdError;
 use std::fmt;
@@ -18,6 +18,8 @@ pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
 #[derive(Debug)]
 pub struct Error {
     inner: BoxError,
+    /// Status of the failed HTTP response this error came from, if any.
+    status: Option<StatusCode>,
 }
 
 impl Error {
@@ -26,6 +28,31 @@ impl Error {
     pub fn new(error: impl Into<BoxError>) -> Self {
         Self {
             inner: error.into(),
+            status: None,
+        }
+    }
+
+    #[allow(dead_code)]
+    /// Create an `Error` for a failed HTTP response with `status`.
+    pub fn with_status(status: StatusCode, error: impl Into<BoxError>) -> Self {
+        Self {
+            inner: error.into(),
+            status: Some(status),
+        }
+    }
+
+    #[allow(dead_code)]
+    /// Status of the failed HTTP response this error came from, if any.
+    pub fn status(&self) -> Option<StatusCode> {
+        self.status
+    }
+
+    #[allow(dead_code)]
+    /// Prefix the message with `context`, keeping the status.
+    pub fn context(self, context: &str) -> Self {
+        Self {
+            inner: format!("{}: {}", context, self.inner).into(),
+            status: self.status,
         }
     }
 
@@ -50,7 +77,10 @@ impl StdError for Error {
 
 impl From<Box<dyn std::error::Error + Send + Sync>> for Error {
     fn from(error: Box<dyn std::error::Error + Send + Sync>) -> Self {
-        Self { inner: error }
+        Self {
+            inner: error,
+            status: None,
+        }
     }
 }
 
@@ -69,6 +99,8 @@ error_from!(reqwest::Error);
 error_from!(Utf8Error);
 error_from!(hyper::header::InvalidHeaderValue);
 error_from!(axum::http::Error);
+error_from!(mongodb::error::Error);
+error_from!(mongodb::bson::ser::Error);
 
 impl IntoResponse for Error {
     fn into_response(self) -> Response {
@@ -106,6 +138,7 @@ pub mod macros {
                 fn from(error: $typ) -> Self {
                     Self {
                         inner: error.into(),
+                        status: None,
                     }
                 }
             }
@@ -1,6 +1,11 @@
 pub mod asana;
 mod error;
 
+/// Shared with the server so that tools creating webhooks record their handshake secrets.
+pub mod webhooks {
+    pub mod secrets;
+}
+
 pub mod prelude {
     pub use crate::asana::{self, methods::*};
 }
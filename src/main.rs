@@ -1,4 +1,15 @@
-use crate::{config::AppConfig, mpsc::ChannelReceiver};
+use crate::{
+    admin::AdminState,
+    config::AppConfig,
+    mpsc::{ChannelReceiver, PausedResources},
+    webhooks::{
+        deliveries::{InMemoryDeliveryStore, MongoDeliveryStore},
+        monitor::{DeliveryTracker, WebhookMonitor},
+        queue::DeliveryQueue,
+        reconciler::Reconciler,
+        secrets::{MongoSecretStore, WebhookSecrets},
+    },
+};
 use axum::{body::Body, response::Response};
 use error::Error;
 use hyper::StatusCode;
@@ -11,12 +22,14 @@ use tokio::sync::{mpsc as tokio_mpsc, Mutex};
 use mongodb::bson::doc;
 use serde::{Deserialize, Serialize};
 
+pub mod admin;
 pub mod asana;
 pub mod config;
 pub mod error;
 pub mod mpsc;
 pub mod server;
 pub mod utils;
+pub mod webhooks;
 
 #[allow(clippy::type_complexity)]
 static APP_CONFIG: LazyLock<Mutex<Option<Box<AppConfig>>>> =
@@ -53,7 +66,7 @@ async fn main() -> color_eyre::Result<()> {
     {
         let config_lock = &mut *APP_CONFIG.lock().await;
         if let Some(config) = config_lock {
-            *config = Box::new(new_config);
+            **config = new_config;
         }
     } // This block ensures we drop the lock here.
 
@@ -63,10 +76,60 @@ async fn main() -> color_eyre::Result<()> {
 
     // Setup mpsc
     let (tx, receiver) = tokio_mpsc::channel::<TxMessage>(32);
-    let mut rx = ChannelReceiver::new(receiver);
+    let (secrets, queue, paused) = if arc_config.db_url().is_empty() {
+        tracing::warn!(
+            "DATABASE_URL is not set; webhook secrets, deliveries and paused projects will not \
+             survive a restart"
+        );
+        (
+            WebhookSecrets::in_memory(),
+            DeliveryQueue::new(tx.clone(), InMemoryDeliveryStore::default()).await?,
+            PausedResources::default(),
+        )
+    } else {
+        let db = mongodb::Client::with_uri_str(arc_config.db_url())
+            .await?
+            .database(arc_config.db_name());
+        (
+            WebhookSecrets::new(MongoSecretStore::new(&db)),
+            DeliveryQueue::new(tx.clone(), MongoDeliveryStore::new(&db).await?).await?,
+            PausedResources::load(&db).await?,
+        )
+    };
+    let mut rx = ChannelReceiver::new(receiver, paused.clone());
+    tokio::spawn(queue.clone().run_drainer());
+
+    let tracker = DeliveryTracker::default();
+    let mut admin = AdminState {
+        paused,
+        ..Default::default()
+    };
+    if env::var_os("ASANA_PERSONAL_ACCESS_TOKEN").is_some() {
+        let monitor = WebhookMonitor::new(
+            asana::Client::default(),
+            secrets.clone(),
+            tracker.clone(),
+            tx.clone(),
+        );
+        tokio::spawn(monitor.run());
+        admin.client = Some(Arc::new(asana::Client::default()));
+
+        if let Some(webhooks) = arc_config.webhooks() {
+            admin.reconciler = Some(Arc::new(Reconciler::new(
+                asana::Client::default(),
+                secrets.clone(),
+                webhooks.clone(),
+            )));
+        }
+    } else {
+        tracing::warn!(
+            "ASANA_PERSONAL_ACCESS_TOKEN is not set; webhook monitoring and reconciliation are disabled"
+        );
+    }
 
     // let config = config::config().await.expect("Loads config");
-    let backend = async move { server::serve(&arc_config, &addr, tx).await };
+    let backend =
+        async move { server::serve(&arc_config, addr, tx, secrets, queue, tracker, admin).await };
 
     // single consumer
     tokio::spawn(async move {
@@ -1,16 +1,108 @@
-use crate::error::Error;
+use crate::{asana::types, error::Error};
+use futures::TryStreamExt;
+use mongodb::{
+    bson::{self, doc},
+    Collection, Database,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fmt::{self, Debug};
-use tokio::sync::mpsc;
+use std::sync::Arc;
+use tokio::sync::{mpsc, RwLock};
+
+const PAUSED_COLLECTION: &str = "paused_resources";
 
 #[allow(dead_code)]
 pub struct ChannelReceiver {
     receiver: mpsc::Receiver<TxMessage>,
     next_id: u32,
+    paused: PausedResources,
+}
+
+/// Resources whose replication is paused from the admin API. Events for them are dropped by
+/// the worker; resuming requests a resync to catch up. Persisted when loaded from a database,
+/// otherwise held in memory and lost on restart.
+#[derive(Debug, Clone, Default)]
+pub struct PausedResources {
+    inner: Arc<RwLock<HashSet<String>>>,
+    collection: Option<Collection<PausedResource>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PausedResource {
+    #[serde(rename = "_id")]
+    resource_gid: String,
+    paused_at: bson::DateTime,
+}
+
+impl PausedResources {
+    /// Pauses stored in `db`, kept there as they change.
+    pub async fn load(db: &Database) -> Result<Self, Error> {
+        let collection: Collection<PausedResource> = db.collection(PAUSED_COLLECTION);
+        let paused: Vec<PausedResource> = collection.find(doc! {}).await?.try_collect().await?;
+
+        Ok(Self {
+            inner: Arc::new(RwLock::new(
+                paused
+                    .into_iter()
+                    .map(|paused| paused.resource_gid)
+                    .collect(),
+            )),
+            collection: Some(collection),
+        })
+    }
+
+    /// Whether pauses survive a restart.
+    pub fn is_persistent(&self) -> bool {
+        self.collection.is_some()
+    }
+
+    pub async fn pause(&self, resource_gid: &str) -> Result<(), Error> {
+        let mut inner = self.inner.write().await;
+        if let Some(collection) = &self.collection {
+            let paused = PausedResource {
+                resource_gid: resource_gid.to_string(),
+                paused_at: bson::DateTime::now(),
+            };
+            collection
+                .replace_one(doc! { "_id": resource_gid }, &paused)
+                .upsert(true)
+                .await?;
+        }
+        inner.insert(resource_gid.to_string());
+
+        Ok(())
+    }
+
+    /// Returns `false` if the resource was not paused.
+    pub async fn resume(&self, resource_gid: &str) -> Result<bool, Error> {
+        let mut inner = self.inner.write().await;
+        if let Some(collection) = &self.collection {
+            collection.delete_one(doc! { "_id": resource_gid }).await?;
+        }
+
+        Ok(inner.remove(resource_gid))
+    }
+
+    pub async fn is_paused(&self, resource_gid: &str) -> bool {
+        self.inner.read().await.contains(resource_gid)
+    }
 }
 
 #[derive(Debug)]
 pub enum TxMessage {
-    RunTask { timestamp: String },
+    RunTask {
+        timestamp: String,
+    },
+    /// An event delivered to the webhook on `resource_gid`.
+    WebhookEvent {
+        resource_gid: String,
+        event: Box<types::WebhookEvent>,
+    },
+    /// Events for `resource_gid` may have been missed; bring it back up to date.
+    Resync {
+        resource_gid: String,
+    },
 }
 
 impl fmt::Display for TxMessage {
@@ -20,10 +112,11 @@ impl fmt::Display for TxMessage {
 }
 
 impl ChannelReceiver {
-    pub fn new(receiver: mpsc::Receiver<TxMessage>) -> Self {
+    pub fn new(receiver: mpsc::Receiver<TxMessage>, paused: PausedResources) -> Self {
         ChannelReceiver {
             receiver,
             next_id: 0,
+            paused,
         }
     }
 
@@ -32,8 +125,37 @@ impl ChannelReceiver {
             tracing::info!("\n\r --> run(): Blocking for next message.");
 
             match msg {
-                TxMessage::RunTask { timestamp: _ } => {
-                    unimplemented!()
+                TxMessage::RunTask { timestamp } => {
+                    tracing::warn!("Ignoring `RunTask` at {}: not implemented yet", timestamp);
+                }
+                TxMessage::WebhookEvent {
+                    resource_gid,
+                    event,
+                } => {
+                    if self.paused.is_paused(&resource_gid).await {
+                        tracing::debug!(
+                            "Replication paused for `gid: {}`, skipping event",
+                            resource_gid
+                        );
+                        continue;
+                    }
+                    self.next_id += 1;
+                    tracing::info!(
+                        "Event #{} on webhook `gid: {}`: {} {} `gid: {}`{}",
+                        self.next_id,
+                        resource_gid,
+                        event.action,
+                        event.resource.resource_type,
+                        event.resource.gid,
+                        event
+                            .change
+                            .as_ref()
+                            .map(|change| format!(" ({} {})", change.action, change.field))
+                            .unwrap_or_default()
+                    );
+                }
+                TxMessage::Resync { resource_gid } => {
+                    tracing::info!("Resync requested for `gid: {}`", resource_gid);
                 }
             };
         }
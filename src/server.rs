@@ -1,7 +1,18 @@
-use crate::{error::Error, mpsc::TxMessage, return_json, AppConfig, APP_CONFIG};
+use crate::{
+    admin::{admin_router, AdminState},
+    asana::types,
+    config::{CorsConfig, OriginPattern},
+    error::Error,
+    mpsc::TxMessage,
+    return_json,
+    webhooks::{
+        self, monitor::DeliveryTracker, queue::DeliveryQueue, secrets::WebhookSecrets, signature,
+    },
+    AppConfig,
+};
 use axum::{
-    extract::{self, DefaultBodyLimit, Extension, Path},
-    http::HeaderValue,
+    body::Bytes,
+    extract::{DefaultBodyLimit, Extension, Path},
     response::{IntoResponse, Response},
     routing::{get, post, Router},
 };
@@ -19,53 +30,101 @@ use tower_http::{
 };
 use tracing::Level;
 
-pub async fn serve(config: &AppConfig, addr: &str, handle: mpsc::Sender<TxMessage>) {
-    let mut app = api_router();
-
-    app = allow_cors(app);
-    app = add_middleware(config, app, handle);
+pub async fn serve(
+    config: &AppConfig,
+    addr: &str,
+    handle: mpsc::Sender<TxMessage>,
+    secrets: WebhookSecrets,
+    queue: DeliveryQueue,
+    tracker: DeliveryTracker,
+    admin: AdminState,
+) {
+    let mut app = api_router(config);
+    app = add_middleware(config, app, handle, secrets, queue, tracker, admin.clone());
+
+    // Bind before reconciling: creating a webhook waits on Asana's handshake to this server.
+    let server = axum::Server::bind(&addr.parse().unwrap());
+    if let Some(reconciler) = admin.reconciler {
+        tokio::spawn(async move {
+            if let Err(err) = reconciler.reconcile().await {
+                tracing::error!("Startup webhook reconcile failed: {}", err);
+            }
+        });
+    }
 
-    axum::Server::bind(&addr.parse().unwrap())
-        .serve(app.into_make_service())
-        .await
-        .unwrap();
+    server.serve(app.into_make_service()).await.unwrap();
 }
 
-pub fn get_middleware(config: &AppConfig, handle: mpsc::Sender<TxMessage>) -> Router {
-    let mut app = api_router();
-    app = add_middleware(config, app, handle);
+pub fn get_middleware(
+    config: &AppConfig,
+    handle: mpsc::Sender<TxMessage>,
+    secrets: WebhookSecrets,
+    queue: DeliveryQueue,
+    tracker: DeliveryTracker,
+    admin: AdminState,
+) -> Router {
+    let mut app = api_router(config);
+    app = add_middleware(config, app, handle, secrets, queue, tracker, admin);
 
     app
 }
 
-struct CorsOrigins<'a>(pub(crate) &'a Vec<HeaderValue>);
+struct CorsOrigins<'a>(pub(crate) &'a [OriginPattern]);
 
 impl From<CorsOrigins<'_>> for AllowOrigin {
     fn from(value: CorsOrigins<'_>) -> Self {
-        AllowOrigin::list(value.0.to_owned())
+        let patterns = value.0.to_vec();
+
+        AllowOrigin::predicate(move |origin, _| {
+            patterns.iter().any(|pattern| pattern.matches(origin))
+        })
     }
 }
 
 impl<'a> fmt::Display for CorsOrigins<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.0.iter().fold(Ok(()), |result, origin| {
-            result.and_then(|_| writeln!(f, "{:?}", origin))
-        })
+        for (i, origin) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", origin)?;
+        }
+
+        Ok(())
     }
 }
 
-fn allow_cors(router: Router) -> Router {
-    let origins = ["http://localhost:9001".parse().unwrap()];
-
-    let cors = CorsLayer::new()
-        .allow_origin(origins)
-        .allow_headers(Any)
-        .allow_methods(Any);
+fn cors_layer(group: &str, cors: &CorsConfig) -> CorsLayer {
+    let mut layer = CorsLayer::new().allow_credentials(cors.allow_credentials);
 
-    router.layer(cors)
+    layer = match &cors.origins {
+        Some(origins) => {
+            tracing::info!("CORS origins for {}: {}", group, CorsOrigins(origins));
+            layer.allow_origin(CorsOrigins(origins))
+        }
+        None => layer.allow_origin(Any),
+    };
+    layer = match &cors.methods {
+        Some(methods) => layer.allow_methods(methods.clone()),
+        None => layer.allow_methods(Any),
+    };
+    layer = match &cors.headers {
+        Some(headers) => layer.allow_headers(headers.clone()),
+        None => layer.allow_headers(Any),
+    };
+
+    layer
 }
 
-fn add_middleware(config: &AppConfig, router: Router, handle: mpsc::Sender<TxMessage>) -> Router {
+fn add_middleware(
+    config: &AppConfig,
+    router: Router,
+    handle: mpsc::Sender<TxMessage>,
+    secrets: WebhookSecrets,
+    queue: DeliveryQueue,
+    tracker: DeliveryTracker,
+    admin: AdminState,
+) -> Router {
     router.layer(
         ServiceBuilder::new()
             .layer(
@@ -76,20 +135,38 @@ fn add_middleware(config: &AppConfig, router: Router, handle: mpsc::Sender<TxMes
             .layer(CatchPanicLayer::new())
             .layer(Extension(config.clone()))
             .layer(Extension(handle))
+            .layer(Extension(secrets))
+            .layer(Extension(queue))
+            .layer(Extension(tracker))
+            .layer(Extension(admin))
             .layer(DefaultBodyLimit::max(20971520)),
     )
 }
 
-fn api_router() -> Router {
+// Webhook deliveries come from Asana's servers, not browsers, so that route gets no CORS.
+fn api_router(config: &AppConfig) -> Router {
+    let public = Router::new()
+        .route("/health", get(handle_health_get))
+        .route("/metrics", get(handle_metrics_get))
+        .layer(cors_layer("public routes", config.cors()));
+    let admin = admin_router().layer(cors_layer("/admin", config.admin_cors()));
+
     Router::new()
         .route("/receive-webhook/:gid", post(handle_receive_webhook))
-        .route("/health", get(handle_health_get))
+        .merge(public)
+        .nest("/admin", admin)
 }
 
 pub async fn handle_health_get() -> Result<Response, Error> {
     Ok(return_json(json!({ "status": "success" }), None)?.into_response())
 }
 
+pub async fn handle_metrics_get(
+    Extension(queue): Extension<DeliveryQueue>,
+) -> Result<Response, Error> {
+    Ok(return_json(json!(queue.metrics()), None)?.into_response())
+}
+
 fn return_webhook(secret: &str) -> Result<Response<axum::body::Body>, Error> {
     let resp = Response::builder()
         .status(StatusCode::OK)
@@ -101,41 +178,76 @@ fn return_webhook(secret: &str) -> Result<Response<axum::body::Body>, Error> {
     Ok(resp)
 }
 
+fn reject_unauthorized(gid: u64, reason: &str) -> Result<Response, Error> {
+    tracing::warn!(target: "security", gid, "Rejected webhook delivery: {}", reason);
+
+    Ok(return_json(
+        json!({ "status": "error: unauthorized" }),
+        Some(StatusCode::UNAUTHORIZED),
+    )?
+    .into_response())
+}
+
 // During the handshake, the response does not contain a JSON payload.
 pub async fn handle_receive_webhook(
     headers: axum::http::header::HeaderMap,
-    Path(_gid): Path<u64>,
-    Extension(_actor_handle): Extension<mpsc::Sender<TxMessage>>,
-    payload: Option<extract::Json<serde_json::Value>>,
+    Path(gid): Path<u64>,
+    Extension(secrets): Extension<WebhookSecrets>,
+    Extension(queue): Extension<DeliveryQueue>,
+    Extension(tracker): Extension<DeliveryTracker>,
+    body: Bytes,
 ) -> Result<Response, Error> {
-    {
-        let config_lock = &mut *APP_CONFIG.lock().await;
-        if let Some(_config) = config_lock {
-            todo!()
-        }
-    }
+    let resource_gid = gid.to_string();
 
-    // TODO: Received events from Asana should be persisted to the DB.
-    if let Some(payload) = payload {
-        tracing::info!("Payload: {:?}", payload);
-    } else {
-        tracing::info!("Payload: None");
-    }
-
-    tracing::info!("Headers: {:?}", headers);
-    if let Some(h) = headers.get("X-Hook-Secret") {
-        if let Ok(secret) = h.to_str() {
-            // TODO: the secret should be stored in a database, as this is used to verify future
-            // webhook requests
-
-            return Ok(return_webhook(secret)?.into_response());
-        } else {
+    if let Some(h) = headers.get(webhooks::HOOK_SECRET_HEADER) {
+        let Ok(secret) = h.to_str() else {
             return Ok(
                 return_json(json!({ "status": "error: Asana secret is missing?" }), None)?
                     .into_response(),
             );
+        };
+
+        if !secrets.record_handshake(&resource_gid, secret).await? {
+            return reject_unauthorized(gid, "unsolicited handshake");
         }
-    } else {
-        Ok(return_json(json!({ "status": "success" }), None)?.into_response())
+
+        return Ok(return_webhook(secret)?.into_response());
+    }
+
+    let Some(secret) = secrets.get(&resource_gid).await? else {
+        return reject_unauthorized(gid, "no handshake secret held for resource");
+    };
+    let Some(sig) = headers
+        .get(webhooks::HOOK_SIGNATURE_HEADER)
+        .and_then(|h| h.to_str().ok())
+    else {
+        return reject_unauthorized(gid, "missing X-Hook-Signature");
+    };
+    if !signature::verify(&secret, &body, sig) {
+        return reject_unauthorized(gid, "X-Hook-Signature mismatch");
+    }
+    tracker.record(&resource_gid).await;
+
+    let payload = match serde_json::from_slice::<types::WebhookEvents>(&body) {
+        Ok(payload) => payload,
+        Err(err) => {
+            tracing::error!("Unreadable webhook payload for `gid: {}`: {}", gid, err);
+
+            return Ok(return_json(
+                json!({ "status": "error: invalid payload" }),
+                Some(StatusCode::BAD_REQUEST),
+            )?
+            .into_response());
+        }
+    };
+
+    if payload.events.is_empty() {
+        tracing::debug!("Heartbeat received for `gid: {}`", gid);
+        return Ok(return_json(json!({ "status": "success" }), None)?.into_response());
     }
+    // Parsing succeeded, so the body is valid UTF-8.
+    let body = String::from_utf8_lossy(&body).into_owned();
+    queue.enqueue(&resource_gid, body, payload.events).await?;
+
+    Ok(return_json(json!({ "status": "success" }), None)?.into_response())
 }
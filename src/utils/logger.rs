@@ -1,5 +1,5 @@
 use super::colored;
-use super::COLOR;
+use super::Rgb;
 use tracing;
 
 pub enum Level {
@@ -7,11 +7,11 @@ pub enum Level {
 }
 
 pub trait ColorTrait {
-    fn color(&self) -> COLOR;
+    fn color(&self) -> Rgb;
 }
-pub struct Color(pub COLOR);
+pub struct Color(pub Rgb);
 impl ColorTrait for Color {
-    fn color(&self) -> COLOR {
+    fn color(&self) -> Rgb {
         self.0
     }
 }
@@ -0,0 +1,46 @@
+//! Incoming Asana webhook deliveries.
+
+use crate::{
+    asana::{self, types},
+    error::Error,
+};
+
+pub mod dedup;
+pub mod deliveries;
+pub mod monitor;
+pub mod queue;
+pub mod reconciler;
+pub mod secrets;
+pub mod signature;
+
+/// Sent by Asana on the handshake request, and echoed back to confirm the webhook.
+pub const HOOK_SECRET_HEADER: &str = "X-Hook-Secret";
+/// HMAC-SHA256 of the raw request body, hex encoded, sent on every delivery after the handshake.
+pub const HOOK_SIGNATURE_HEADER: &str = "X-Hook-Signature";
+
+/// Every webhook in the workspace delivering to our target URL.
+pub async fn list_our_webhooks(
+    client: &impl asana::HTTPClient,
+    workspace_gid: &str,
+) -> Result<Vec<types::WebhookData>, Error> {
+    let target_prefix = asana::methods::webhook_target("");
+    let mut webhooks = Vec::new();
+    let mut offset: Option<String> = None;
+
+    loop {
+        let (page, _) =
+            asana::methods::get_webhooks(client, workspace_gid, None, offset.as_deref()).await?;
+        webhooks.extend(
+            page.data
+                .into_iter()
+                .filter(|webhook| webhook.target.starts_with(&target_prefix)),
+        );
+
+        match page.next_page {
+            Some(next_page) => offset = Some(next_page.offset),
+            None => break,
+        }
+    }
+
+    Ok(webhooks)
+}
@@ -0,0 +1,222 @@
+//! Drops redelivered and out-of-order webhook events before they reach the worker.
+//!
+//! Asana retries deliveries it did not see acknowledged, and does not guarantee the order of
+//! events for a resource. An event is a duplicate if an identical one was admitted within the
+//! retention window, and stale if it was created before the newest event already admitted for
+//! the same resource. Both are scoped to the webhook that received the event, since the same
+//! change is delivered once to each webhook whose resource it touches.
+
+use crate::asana::{dates::DateTime, types};
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, HashSet, VecDeque},
+    hash::{Hash, Hasher},
+    time::{Duration, Instant},
+};
+
+/// How long an admitted event is remembered.
+pub const RETENTION: Duration = Duration::from_secs(60 * 60);
+/// Upper bound on remembered events, regardless of age.
+pub const CAPACITY: usize = 10_000;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Admitted {
+    pub duplicates: usize,
+    pub stale: usize,
+}
+
+#[derive(Debug)]
+pub struct EventFilter {
+    retention: Duration,
+    capacity: usize,
+    seen: HashSet<u64>,
+    /// Admission order of `seen`, for expiry.
+    order: VecDeque<(u64, Instant)>,
+    /// Newest `created_at` admitted per (webhook resource gid, event resource gid), and when it
+    /// was last updated.
+    versions: HashMap<(String, String), (DateTime, Instant)>,
+}
+
+impl Default for EventFilter {
+    fn default() -> Self {
+        Self::new(RETENTION, CAPACITY)
+    }
+}
+
+impl EventFilter {
+    pub fn new(retention: Duration, capacity: usize) -> Self {
+        Self {
+            retention,
+            capacity,
+            seen: HashSet::new(),
+            order: VecDeque::new(),
+            versions: HashMap::new(),
+        }
+    }
+
+    /// Keep the events delivered to the webhook on `webhook_resource_gid` that are neither
+    /// duplicates nor stale, in order, and remember them.
+    pub fn admit(
+        &mut self,
+        webhook_resource_gid: &str,
+        events: Vec<types::WebhookEvent>,
+    ) -> (Vec<types::WebhookEvent>, Admitted) {
+        let now = Instant::now();
+        self.expire(now);
+
+        let mut counts = Admitted::default();
+        let mut admitted = Vec::with_capacity(events.len());
+        for event in events {
+            let key = identity(webhook_resource_gid, &event);
+            if self.seen.contains(&key) {
+                counts.duplicates += 1;
+                continue;
+            }
+
+            // Several changes can share a timestamp, so only strictly older events are stale.
+            let version = (webhook_resource_gid.to_string(), event.resource.gid.clone());
+            if let Some((latest, _)) = self.versions.get(&version) {
+                if event.created_at < *latest {
+                    counts.stale += 1;
+                    continue;
+                }
+            }
+
+            self.versions.insert(version, (event.created_at, now));
+            self.seen.insert(key);
+            self.order.push_back((key, now));
+            admitted.push(event);
+        }
+
+        while self.order.len() > self.capacity {
+            if let Some((key, _)) = self.order.pop_front() {
+                self.seen.remove(&key);
+            }
+        }
+
+        (admitted, counts)
+    }
+
+    fn expire(&mut self, now: Instant) {
+        while let Some((key, at)) = self.order.front() {
+            if now.duration_since(*at) < self.retention {
+                break;
+            }
+            self.seen.remove(key);
+            self.order.pop_front();
+        }
+
+        self.versions
+            .retain(|_, (_, at)| now.duration_since(*at) < self.retention);
+    }
+}
+
+/// Events carry no id, so identity is the receiving webhook's resource with the event's resource,
+/// action, timestamp and change together.
+fn identity(webhook_resource_gid: &str, event: &types::WebhookEvent) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    webhook_resource_gid.hash(&mut hasher);
+    event.resource.gid.hash(&mut hasher);
+    event.action.hash(&mut hasher);
+    event.created_at.hash(&mut hasher);
+    event
+        .parent
+        .as_ref()
+        .map(|parent| &parent.gid)
+        .hash(&mut hasher);
+    if let Some(change) = &event.change {
+        change.field.hash(&mut hasher);
+        change.action.hash(&mut hasher);
+        for value in [
+            &change.new_value,
+            &change.added_value,
+            &change.removed_value,
+        ] {
+            value
+                .as_ref()
+                .map(|value| value.to_string())
+                .hash(&mut hasher);
+        }
+    }
+
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event(gid: &str, created_at: &str) -> types::WebhookEvent {
+        serde_json::from_value(json!({
+            "action": "changed",
+            "resource": { "gid": gid, "resource_type": "task" },
+            "created_at": created_at,
+            "change": { "field": "name", "action": "changed" },
+        }))
+        .unwrap()
+    }
+
+    const EARLIER: &str = "2024-01-01T00:00:00.000Z";
+    const LATER: &str = "2024-01-01T00:01:00.000Z";
+
+    #[test]
+    fn drops_redelivered_event() {
+        let mut filter = EventFilter::default();
+        let (admitted, _) = filter.admit("1", vec![event("10", EARLIER)]);
+        assert_eq!(admitted.len(), 1);
+
+        let (admitted, counts) = filter.admit("1", vec![event("10", EARLIER)]);
+        assert!(admitted.is_empty());
+        assert_eq!(counts.duplicates, 1);
+    }
+
+    #[test]
+    fn drops_event_older_than_latest() {
+        let mut filter = EventFilter::default();
+        filter.admit("1", vec![event("10", LATER)]);
+
+        let (admitted, counts) = filter.admit("1", vec![event("10", EARLIER)]);
+        assert!(admitted.is_empty());
+        assert_eq!(counts.stale, 1);
+    }
+
+    #[test]
+    fn keeps_events_sharing_a_timestamp() {
+        let mut filter = EventFilter::default();
+        let mut other = event("10", LATER);
+        other.action = types::EventAction::Added;
+
+        let (admitted, counts) = filter.admit("1", vec![event("10", LATER), other]);
+        assert_eq!(admitted.len(), 2);
+        assert_eq!(counts.duplicates + counts.stale, 0);
+    }
+
+    #[test]
+    fn scopes_to_receiving_webhook() {
+        let mut filter = EventFilter::default();
+        filter.admit("1", vec![event("10", LATER)]);
+
+        let (admitted, counts) = filter.admit("2", vec![event("10", EARLIER), event("10", LATER)]);
+        assert_eq!(admitted.len(), 2);
+        assert_eq!(counts.duplicates + counts.stale, 0);
+    }
+
+    #[test]
+    fn forgets_events_after_retention() {
+        let mut filter = EventFilter::new(Duration::ZERO, CAPACITY);
+        filter.admit("1", vec![event("10", LATER)]);
+
+        let (admitted, _) = filter.admit("1", vec![event("10", EARLIER), event("10", LATER)]);
+        assert_eq!(admitted.len(), 2);
+    }
+
+    #[test]
+    fn forgets_oldest_events_beyond_capacity() {
+        let mut filter = EventFilter::new(RETENTION, 1);
+        filter.admit("1", vec![event("10", EARLIER), event("11", EARLIER)]);
+
+        let (admitted, counts) = filter.admit("1", vec![event("10", EARLIER)]);
+        assert_eq!(admitted.len(), 1);
+        assert_eq!(counts.duplicates, 0);
+    }
+}
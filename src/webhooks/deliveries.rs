@@ -0,0 +1,345 @@
+//! Raw webhook deliveries, persisted before they are acknowledged.
+//!
+//! Settled (dispatched or failed) deliveries are only kept as a recent history: Mongo expires
+//! them [`RETENTION`] after settling and the in-memory store keeps at most [`IN_MEMORY_CAPACITY`].
+//! Spilled deliveries are kept until they have been replayed.
+
+use crate::error::Error;
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use mongodb::{
+    bson::{self, doc},
+    options::IndexOptions,
+    Collection, Database, IndexModel,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+use tokio::sync::RwLock;
+
+const COLLECTION: &str = "webhook_deliveries";
+/// How long dispatched and failed deliveries are kept in Mongo.
+pub const RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+/// Most deliveries the in-memory store holds before dropping the oldest dispatched ones.
+pub const IN_MEMORY_CAPACITY: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    /// Handed to the worker channel.
+    Queued,
+    /// The worker channel was full; waiting for the drainer to enqueue it.
+    Spilled,
+    /// Could not be replayed from storage.
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Delivery {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub resource_gid: String,
+    /// The body exactly as received, i.e. what `X-Hook-Signature` was computed over.
+    pub body: String,
+    pub received_at: bson::DateTime,
+    pub status: DeliveryStatus,
+    /// When the delivery was dispatched or given up on; unset while it is spilled, so it is
+    /// never expired before being replayed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settled_at: Option<bson::DateTime>,
+}
+
+impl Delivery {
+    pub fn new(resource_gid: &str, body: String, status: DeliveryStatus) -> Self {
+        let now = bson::DateTime::now();
+
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            resource_gid: resource_gid.to_string(),
+            body,
+            received_at: now,
+            status,
+            settled_at: (status != DeliveryStatus::Spilled).then_some(now),
+        }
+    }
+}
+
+/// Narrows [`DeliveryStore::recent`]; unset fields match everything.
+#[derive(Debug, Default, Clone)]
+pub struct DeliveryFilter {
+    pub resource_gid: Option<String>,
+    pub status: Option<DeliveryStatus>,
+    /// Only deliveries received at or after this instant.
+    pub since: Option<bson::DateTime>,
+}
+
+impl DeliveryFilter {
+    fn matches(&self, delivery: &Delivery) -> bool {
+        self.resource_gid
+            .as_ref()
+            .is_none_or(|gid| *gid == delivery.resource_gid)
+            && self.status.is_none_or(|status| status == delivery.status)
+            && self.since.is_none_or(|since| delivery.received_at >= since)
+    }
+
+    fn to_document(&self) -> Result<bson::Document, Error> {
+        let mut filter = doc! {};
+        if let Some(resource_gid) = &self.resource_gid {
+            filter.insert("resource_gid", resource_gid);
+        }
+        if let Some(status) = &self.status {
+            filter.insert("status", bson::to_bson(status)?);
+        }
+        if let Some(since) = self.since {
+            filter.insert("received_at", doc! { "$gte": since });
+        }
+
+        Ok(filter)
+    }
+}
+
+/// Storage backend for [`Delivery`] records.
+#[async_trait]
+pub trait DeliveryStore: Send + Sync {
+    async fn insert(&self, delivery: &Delivery) -> Result<(), Error>;
+    async fn set_status(&self, id: &str, status: DeliveryStatus) -> Result<(), Error>;
+    /// Up to `limit` deliveries with `status`, oldest first.
+    async fn with_status(
+        &self,
+        status: DeliveryStatus,
+        limit: usize,
+    ) -> Result<Vec<Delivery>, Error>;
+    async fn count(&self, status: DeliveryStatus) -> Result<usize, Error>;
+    /// Up to `limit` deliveries matching `filter`, newest first.
+    async fn recent(&self, filter: &DeliveryFilter, limit: usize) -> Result<Vec<Delivery>, Error>;
+}
+
+#[derive(Debug, Default)]
+pub struct InMemoryDeliveryStore {
+    inner: RwLock<Deliveries>,
+}
+
+#[derive(Debug, Default)]
+struct Deliveries {
+    by_id: HashMap<String, Delivery>,
+    /// Ids in insertion order, for eviction.
+    order: VecDeque<String>,
+}
+
+impl Deliveries {
+    fn values(&self) -> impl Iterator<Item = &Delivery> {
+        self.by_id.values()
+    }
+
+    /// Drop the oldest deliveries that are not waiting to be replayed until within capacity.
+    fn evict(&mut self, capacity: usize) {
+        let mut index = 0;
+        while self.by_id.len() > capacity && index < self.order.len() {
+            let spilled = self
+                .by_id
+                .get(&self.order[index])
+                .is_some_and(|delivery| delivery.status == DeliveryStatus::Spilled);
+            if spilled {
+                index += 1;
+            } else if let Some(id) = self.order.remove(index) {
+                self.by_id.remove(&id);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl DeliveryStore for InMemoryDeliveryStore {
+    async fn insert(&self, delivery: &Delivery) -> Result<(), Error> {
+        let mut inner = self.inner.write().await;
+        if inner
+            .by_id
+            .insert(delivery.id.clone(), delivery.clone())
+            .is_none()
+        {
+            inner.order.push_back(delivery.id.clone());
+        }
+        inner.evict(IN_MEMORY_CAPACITY);
+
+        Ok(())
+    }
+
+    async fn set_status(&self, id: &str, status: DeliveryStatus) -> Result<(), Error> {
+        if let Some(delivery) = self.inner.write().await.by_id.get_mut(id) {
+            delivery.status = status;
+            delivery.settled_at = (status != DeliveryStatus::Spilled).then(bson::DateTime::now);
+        }
+
+        Ok(())
+    }
+
+    async fn with_status(
+        &self,
+        status: DeliveryStatus,
+        limit: usize,
+    ) -> Result<Vec<Delivery>, Error> {
+        let mut deliveries: Vec<Delivery> = self
+            .inner
+            .read()
+            .await
+            .values()
+            .filter(|delivery| delivery.status == status)
+            .cloned()
+            .collect();
+        deliveries.sort_by_key(|delivery| delivery.received_at);
+        deliveries.truncate(limit);
+
+        Ok(deliveries)
+    }
+
+    async fn count(&self, status: DeliveryStatus) -> Result<usize, Error> {
+        Ok(self
+            .inner
+            .read()
+            .await
+            .values()
+            .filter(|delivery| delivery.status == status)
+            .count())
+    }
+
+    async fn recent(&self, filter: &DeliveryFilter, limit: usize) -> Result<Vec<Delivery>, Error> {
+        let mut deliveries: Vec<Delivery> = self
+            .inner
+            .read()
+            .await
+            .values()
+            .filter(|delivery| filter.matches(delivery))
+            .cloned()
+            .collect();
+        deliveries.sort_by_key(|delivery| std::cmp::Reverse(delivery.received_at));
+        deliveries.truncate(limit);
+
+        Ok(deliveries)
+    }
+}
+
+pub struct MongoDeliveryStore {
+    collection: Collection<Delivery>,
+}
+
+impl MongoDeliveryStore {
+    /// Open the collection, ensuring the TTL index that expires settled deliveries.
+    pub async fn new(db: &Database) -> Result<Self, Error> {
+        let collection: Collection<Delivery> = db.collection(COLLECTION);
+        let expiry = IndexModel::builder()
+            .keys(doc! { "settled_at": 1 })
+            .options(IndexOptions::builder().expire_after(RETENTION).build())
+            .build();
+        collection.create_index(expiry).await?;
+
+        Ok(Self { collection })
+    }
+}
+
+#[async_trait]
+impl DeliveryStore for MongoDeliveryStore {
+    async fn insert(&self, delivery: &Delivery) -> Result<(), Error> {
+        self.collection.insert_one(delivery).await?;
+
+        Ok(())
+    }
+
+    async fn set_status(&self, id: &str, status: DeliveryStatus) -> Result<(), Error> {
+        self.collection
+            .update_one(
+                doc! { "_id": id },
+                doc! { "$set": {
+                    "status": bson::to_bson(&status)?,
+                    "settled_at": (status != DeliveryStatus::Spilled).then(bson::DateTime::now),
+                } },
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn with_status(
+        &self,
+        status: DeliveryStatus,
+        limit: usize,
+    ) -> Result<Vec<Delivery>, Error> {
+        let cursor = self
+            .collection
+            .find(doc! { "status": bson::to_bson(&status)? })
+            .sort(doc! { "received_at": 1 })
+            .limit(limit as i64)
+            .await?;
+
+        Ok(cursor.try_collect().await?)
+    }
+
+    async fn count(&self, status: DeliveryStatus) -> Result<usize, Error> {
+        let count = self
+            .collection
+            .count_documents(doc! { "status": bson::to_bson(&status)? })
+            .await?;
+
+        Ok(count as usize)
+    }
+
+    async fn recent(&self, filter: &DeliveryFilter, limit: usize) -> Result<Vec<Delivery>, Error> {
+        let cursor = self
+            .collection
+            .find(filter.to_document()?)
+            .sort(doc! { "received_at": -1 })
+            .limit(limit as i64)
+            .await?;
+
+        Ok(cursor.try_collect().await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deliveries(statuses: &[DeliveryStatus]) -> Deliveries {
+        let mut deliveries = Deliveries::default();
+        for (index, status) in statuses.iter().enumerate() {
+            let mut delivery = Delivery::new("1", String::new(), *status);
+            delivery.id = index.to_string();
+            deliveries.order.push_back(delivery.id.clone());
+            deliveries.by_id.insert(delivery.id.clone(), delivery);
+        }
+
+        deliveries
+    }
+
+    #[test]
+    fn evicts_oldest_settled_deliveries() {
+        use DeliveryStatus::*;
+        let mut deliveries = deliveries(&[Queued, Spilled, Failed, Queued]);
+        deliveries.evict(2);
+
+        let mut kept: Vec<&str> = deliveries.by_id.keys().map(String::as_str).collect();
+        kept.sort();
+        assert_eq!(kept, ["1", "3"]);
+        assert_eq!(deliveries.order, ["1", "3"]);
+    }
+
+    #[test]
+    fn never_evicts_spilled_deliveries() {
+        use DeliveryStatus::*;
+        let mut deliveries = deliveries(&[Spilled, Spilled, Spilled]);
+        deliveries.evict(1);
+
+        assert_eq!(deliveries.by_id.len(), 3);
+    }
+
+    #[test]
+    fn settles_on_dispatch() {
+        assert!(Delivery::new("1", String::new(), DeliveryStatus::Spilled)
+            .settled_at
+            .is_none());
+        assert!(Delivery::new("1", String::new(), DeliveryStatus::Queued)
+            .settled_at
+            .is_some());
+    }
+}
@@ -0,0 +1,273 @@
+//! Watches the health of our webhooks.
+//!
+//! Asana sends a heartbeat to every webhook every 8 hours and deletes webhooks whose deliveries
+//! keep failing. The monitor alerts when a webhook goes quiet or starts failing, and recreates
+//! any that were deleted, asking the worker to resync the resource to cover the missed events.
+
+use super::secrets::{WebhookSecret, WebhookSecrets};
+use crate::{asana, error::Error, mpsc::TxMessage};
+use hyper::StatusCode;
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::{mpsc, RwLock};
+
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+/// An hour of slack over Asana's heartbeat interval.
+pub const STALE_AFTER: Duration = Duration::from_secs(9 * 60 * 60);
+
+/// Time of the last verified delivery per resource gid, heartbeats included.
+#[derive(Debug, Clone)]
+pub struct DeliveryTracker {
+    started: Instant,
+    last_delivery: Arc<RwLock<HashMap<String, Instant>>>,
+}
+
+impl Default for DeliveryTracker {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            last_delivery: Arc::default(),
+        }
+    }
+}
+
+impl DeliveryTracker {
+    pub async fn record(&self, resource_gid: &str) {
+        self.last_delivery
+            .write()
+            .await
+            .insert(resource_gid.to_string(), Instant::now());
+    }
+
+    /// Time since the last delivery, if one has arrived since startup.
+    pub async fn last_delivery(&self, resource_gid: &str) -> Option<Duration> {
+        self.last_delivery
+            .read()
+            .await
+            .get(resource_gid)
+            .map(Instant::elapsed)
+    }
+
+    /// Time since the last delivery, or since startup if none has arrived yet.
+    pub async fn since_last_delivery(&self, resource_gid: &str) -> Duration {
+        self.last_delivery
+            .read()
+            .await
+            .get(resource_gid)
+            .unwrap_or(&self.started)
+            .elapsed()
+    }
+}
+
+pub struct WebhookMonitor<C> {
+    client: C,
+    secrets: WebhookSecrets,
+    tracker: DeliveryTracker,
+    handle: mpsc::Sender<TxMessage>,
+}
+
+impl<C: asana::HTTPClient + Sync> WebhookMonitor<C> {
+    pub fn new(
+        client: C,
+        secrets: WebhookSecrets,
+        tracker: DeliveryTracker,
+        handle: mpsc::Sender<TxMessage>,
+    ) -> Self {
+        Self {
+            client,
+            secrets,
+            tracker,
+            handle,
+        }
+    }
+
+    pub async fn run(self) {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(err) = self.check_all().await {
+                tracing::error!("Webhook health check failed: {}", err);
+            }
+        }
+    }
+
+    async fn check_all(&self) -> Result<(), Error> {
+        for listed in self.secrets.list().await? {
+            // Re-read under the lock in case a reconcile replaced the webhook meanwhile
+            let _changes = self.secrets.lock_changes().await;
+            let Some(record) = self.secrets.record(&listed.resource_gid).await? else {
+                continue;
+            };
+            if let Err(err) = self.check(&record).await {
+                tracing::error!(
+                    "Webhook health check for `gid: {}` failed: {}",
+                    record.resource_gid,
+                    err
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn check(&self, record: &WebhookSecret) -> Result<(), Error> {
+        let Some(webhook_gid) = &record.webhook_gid else {
+            return Ok(());
+        };
+        let resource_gid = &record.resource_gid;
+
+        let webhook = match asana::methods::get_webhook(&self.client, webhook_gid).await {
+            Ok((resp, _)) => resp.data,
+            Err(err) if err.status() == Some(StatusCode::NOT_FOUND) => {
+                return self.recreate(record).await
+            }
+            Err(err) => return Err(err),
+        };
+
+        if !webhook.active {
+            tracing::error!(target: "alert", "Webhook `gid: {}` is inactive", webhook_gid);
+        }
+        if let Some(deletion) = webhook.failure_deletion_timestamp {
+            tracing::error!(
+                target: "alert",
+                "Webhook `gid: {}` is failing and will be deleted at {}: {}",
+                webhook_gid,
+                deletion,
+                webhook.last_failure_content.unwrap_or_default()
+            );
+        } else if webhook.last_failure_at > webhook.last_success_at {
+            tracing::warn!(
+                target: "alert",
+                "Webhook `gid: {}` last delivery failed at {}: {}",
+                webhook_gid,
+                webhook.last_failure_at.map(|at| at.to_string()).unwrap_or_default(),
+                webhook.last_failure_content.unwrap_or_default()
+            );
+        }
+
+        let quiet_for = self.tracker.since_last_delivery(resource_gid).await;
+        if quiet_for > STALE_AFTER {
+            tracing::warn!(
+                target: "alert",
+                "No delivery for `gid: {}` in {} minutes",
+                resource_gid,
+                quiet_for.as_secs() / 60
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn recreate(&self, record: &WebhookSecret) -> Result<(), Error> {
+        let resource_gid = &record.resource_gid;
+        tracing::error!(
+            target: "alert",
+            "Webhook `gid: {}` on `gid: {}` was deleted, recreating",
+            record.webhook_gid.as_deref().unwrap_or_default(),
+            resource_gid
+        );
+
+        let webhook = self
+            .secrets
+            .create_webhook(&self.client, resource_gid, &record.filters)
+            .await?;
+        tracing::info!(
+            "Recreated webhook `gid: {}` on `gid: {}`",
+            webhook.data.gid,
+            resource_gid
+        );
+
+        self.handle
+            .send(TxMessage::Resync {
+                resource_gid: resource_gid.to_string(),
+            })
+            .await
+            .map_err(|err| Error::new(format!("Worker channel closed: {}", err)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use serde_json::{json, Value};
+
+    const RESOURCE: &str = "1200";
+
+    /// Fails `GET /webhooks/:gid` with `lookup`, and creates webhooks with a handshake.
+    struct Asana {
+        secrets: WebhookSecrets,
+        lookup: fn() -> Error,
+    }
+
+    #[async_trait]
+    impl asana::HTTPClient for Asana {
+        async fn send_request<T>(
+            &self,
+            method: &str,
+            _endpoint: &str,
+            _params: &[(&str, &str)],
+            _body: Option<String>,
+        ) -> Result<(T, Value), Error>
+        where
+            T: serde::de::DeserializeOwned + std::fmt::Debug,
+        {
+            if method == "GET" {
+                return Err((self.lookup)());
+            }
+            self.secrets.record_handshake(RESOURCE, "secret").await?;
+            let value = json!({ "data": { "gid": "9000", "resource_type": "webhook" } });
+
+            Ok((serde_json::from_value(value.clone())?, value))
+        }
+    }
+
+    async fn check(lookup: fn() -> Error) -> (Result<(), Error>, Option<TxMessage>) {
+        let secrets = WebhookSecrets::in_memory();
+        let client = Asana {
+            secrets: secrets.clone(),
+            lookup,
+        };
+        secrets
+            .create_webhook(&client, RESOURCE, &[])
+            .await
+            .unwrap();
+        let record = secrets.list().await.unwrap().remove(0);
+
+        let (handle, mut receiver) = mpsc::channel(1);
+        let monitor = WebhookMonitor::new(client, secrets, DeliveryTracker::default(), handle);
+        let result = monitor.check(&record).await;
+
+        (result, receiver.try_recv().ok())
+    }
+
+    #[tokio::test]
+    async fn recreates_webhook_on_not_found() {
+        let (result, message) =
+            check(|| Error::with_status(StatusCode::NOT_FOUND, "Request Failure: Status: 404"))
+                .await;
+
+        assert!(result.is_ok());
+        assert!(matches!(
+            message,
+            Some(TxMessage::Resync { resource_gid }) if resource_gid == RESOURCE
+        ));
+    }
+
+    #[tokio::test]
+    async fn keeps_webhook_on_other_failures() {
+        let (result, message) = check(|| {
+            Error::with_status(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Request Failure: Status: 500 / Bytes: 404 Not Found",
+            )
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert!(message.is_none());
+    }
+}
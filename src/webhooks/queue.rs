@@ -0,0 +1,320 @@
+//! Hands verified deliveries to the worker channel without blocking the webhook response.
+//!
+//! Every delivery is persisted first. Events that do not fit in the channel are persisted as a
+//! separate spilled delivery and left for [`DeliveryQueue::run_drainer`] to replay, so Asana is
+//! acknowledged immediately and nothing is dropped.
+
+use super::{
+    dedup::{Admitted, EventFilter},
+    deliveries::{Delivery, DeliveryFilter, DeliveryStatus, DeliveryStore},
+};
+use crate::{asana::types, error::Error, mpsc::TxMessage};
+use serde::Serialize;
+use std::{
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+use tokio::sync::{mpsc, Notify};
+
+/// Deliveries replayed per round trip to the store.
+const DRAIN_BATCH: usize = 32;
+const DRAIN_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Serialize)]
+pub struct QueueMetrics {
+    /// Events waiting in the worker channel.
+    pub queue_depth: usize,
+    pub queue_capacity: usize,
+    /// Deliveries waiting in storage for room in the channel.
+    pub spilled: usize,
+    pub enqueued_total: u64,
+    pub spilled_total: u64,
+    /// Events dropped as redeliveries of an already enqueued event.
+    pub duplicates_total: u64,
+    /// Events dropped as older than one already enqueued for the same resource.
+    pub stale_total: u64,
+}
+
+#[derive(Clone)]
+pub struct DeliveryQueue {
+    sender: mpsc::Sender<TxMessage>,
+    store: Arc<dyn DeliveryStore>,
+    spilled: Arc<AtomicUsize>,
+    enqueued_total: Arc<AtomicU64>,
+    spilled_total: Arc<AtomicU64>,
+    duplicates_total: Arc<AtomicU64>,
+    stale_total: Arc<AtomicU64>,
+    filter: Arc<Mutex<EventFilter>>,
+    notify: Arc<Notify>,
+}
+
+impl DeliveryQueue {
+    pub async fn new(
+        sender: mpsc::Sender<TxMessage>,
+        store: impl DeliveryStore + 'static,
+    ) -> Result<Self, Error> {
+        // Pick up deliveries spilled before a restart.
+        let spilled = store.count(DeliveryStatus::Spilled).await?;
+
+        Ok(Self {
+            sender,
+            store: Arc::new(store),
+            spilled: Arc::new(AtomicUsize::new(spilled)),
+            enqueued_total: Arc::new(AtomicU64::new(0)),
+            spilled_total: Arc::new(AtomicU64::new(0)),
+            duplicates_total: Arc::new(AtomicU64::new(0)),
+            stale_total: Arc::new(AtomicU64::new(0)),
+            filter: Arc::new(Mutex::new(EventFilter::default())),
+            notify: Arc::new(Notify::new()),
+        })
+    }
+
+    pub fn metrics(&self) -> QueueMetrics {
+        QueueMetrics {
+            queue_depth: self.sender.max_capacity() - self.sender.capacity(),
+            queue_capacity: self.sender.max_capacity(),
+            spilled: self.spilled.load(Ordering::Relaxed),
+            enqueued_total: self.enqueued_total.load(Ordering::Relaxed),
+            spilled_total: self.spilled_total.load(Ordering::Relaxed),
+            duplicates_total: self.duplicates_total.load(Ordering::Relaxed),
+            stale_total: self.stale_total.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Recently received deliveries, newest first.
+    pub async fn recent(
+        &self,
+        filter: &DeliveryFilter,
+        limit: usize,
+    ) -> Result<Vec<Delivery>, Error> {
+        self.store.recent(filter, limit).await
+    }
+
+    /// Persist a delivery and enqueue as many of its events as the channel has room for,
+    /// spilling the rest to storage. Never waits on the worker.
+    pub async fn enqueue(
+        &self,
+        resource_gid: &str,
+        body: String,
+        mut events: Vec<types::WebhookEvent>,
+    ) -> Result<(), Error> {
+        // While older deliveries are spilled, newer ones queue behind them to keep their order.
+        let fits = if self.spilled.load(Ordering::Acquire) == 0 {
+            events.len().min(self.sender.capacity())
+        } else {
+            0
+        };
+        let permits = match fits {
+            0 => None,
+            fits => self.sender.try_reserve_many(fits).ok(),
+        };
+
+        let Some(permits) = permits else {
+            let delivery = self.spill(resource_gid, body).await?;
+            tracing::warn!(
+                "Worker channel full, spilled delivery `{}` for `gid: {}`",
+                delivery.id,
+                resource_gid
+            );
+
+            return Ok(());
+        };
+
+        // On a partial spill each event is stored once: the queued delivery keeps only the events
+        // dispatched now, the spilled one the rest.
+        let overflow = events.split_off(fits);
+        let body = if overflow.is_empty() {
+            body
+        } else {
+            let dispatched = types::WebhookEvents { events };
+            let body = serde_json::to_string(&dispatched)?;
+            events = dispatched.events;
+            body
+        };
+        let delivery = Delivery::new(resource_gid, body, DeliveryStatus::Queued);
+        self.store.insert(&delivery).await?;
+        if !overflow.is_empty() {
+            let count = overflow.len();
+            let body = serde_json::to_string(&types::WebhookEvents { events: overflow })?;
+            let spilled = self.spill(resource_gid, body).await?;
+            tracing::warn!(
+                "Worker channel full, spilled {} events of delivery `{}` as `{}`",
+                count,
+                delivery.id,
+                spilled.id
+            );
+        }
+        // Filter only once the events are certain to be sent, so a spilled event is not
+        // remembered as seen before it is replayed. Unused permits are released on drop.
+        let events = self.admit(resource_gid, events);
+        self.enqueued_total
+            .fetch_add(events.len() as u64, Ordering::Relaxed);
+        for (permit, event) in permits.zip(events) {
+            permit.send(TxMessage::WebhookEvent {
+                resource_gid: resource_gid.to_string(),
+                event: Box::new(event),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Persist `body` for the drainer to replay.
+    async fn spill(&self, resource_gid: &str, body: String) -> Result<Delivery, Error> {
+        let delivery = Delivery::new(resource_gid, body, DeliveryStatus::Spilled);
+        self.store.insert(&delivery).await?;
+        self.spilled.fetch_add(1, Ordering::AcqRel);
+        self.spilled_total.fetch_add(1, Ordering::Relaxed);
+        self.notify.notify_one();
+
+        Ok(delivery)
+    }
+
+    fn admit(
+        &self,
+        resource_gid: &str,
+        events: Vec<types::WebhookEvent>,
+    ) -> Vec<types::WebhookEvent> {
+        let (events, Admitted { duplicates, stale }) = match self.filter.lock() {
+            Ok(mut filter) => filter.admit(resource_gid, events),
+            Err(poisoned) => poisoned.into_inner().admit(resource_gid, events),
+        };
+        if duplicates > 0 || stale > 0 {
+            tracing::info!(
+                "Dropped {} duplicate and {} stale webhook events",
+                duplicates,
+                stale
+            );
+        }
+        self.duplicates_total
+            .fetch_add(duplicates as u64, Ordering::Relaxed);
+        self.stale_total.fetch_add(stale as u64, Ordering::Relaxed);
+
+        events
+    }
+
+    /// Replay spilled deliveries into the channel, oldest first, waiting for room as needed.
+    pub async fn run_drainer(self) {
+        loop {
+            if let Err(err) = self.drain().await {
+                tracing::error!("Failed to drain spilled deliveries: {}", err);
+                tokio::time::sleep(DRAIN_RETRY_INTERVAL).await;
+            }
+        }
+    }
+
+    async fn drain(&self) -> Result<(), Error> {
+        let batch = self
+            .store
+            .with_status(DeliveryStatus::Spilled, DRAIN_BATCH)
+            .await?;
+        if batch.is_empty() {
+            self.notify.notified().await;
+            return Ok(());
+        }
+
+        for delivery in batch {
+            let status = match serde_json::from_str::<types::WebhookEvents>(&delivery.body) {
+                Ok(payload) => {
+                    for event in self.admit(&delivery.resource_gid, payload.events) {
+                        self.sender
+                            .send(TxMessage::WebhookEvent {
+                                resource_gid: delivery.resource_gid.clone(),
+                                event: Box::new(event),
+                            })
+                            .await
+                            .map_err(|err| Error::new(format!("Worker channel closed: {}", err)))?;
+                        self.enqueued_total.fetch_add(1, Ordering::Relaxed);
+                    }
+                    DeliveryStatus::Queued
+                }
+                Err(err) => {
+                    tracing::error!("Spilled delivery `{}` is unreadable: {}", delivery.id, err);
+                    DeliveryStatus::Failed
+                }
+            };
+            self.store.set_status(&delivery.id, status).await?;
+            self.spilled.fetch_sub(1, Ordering::AcqRel);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::webhooks::deliveries::InMemoryDeliveryStore;
+    use serde_json::json;
+
+    fn events(count: usize) -> Vec<types::WebhookEvent> {
+        (0..count)
+            .map(|index| {
+                serde_json::from_value(json!({
+                    "action": "changed",
+                    "resource": { "gid": index.to_string(), "resource_type": "task" },
+                    "created_at": "2024-01-01T00:00:00.000Z",
+                }))
+                .unwrap()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn spills_only_the_overflow() {
+        let (sender, mut receiver) = mpsc::channel(2);
+        let queue = DeliveryQueue::new(sender, InMemoryDeliveryStore::default())
+            .await
+            .unwrap();
+        queue
+            .enqueue("1", String::from("{}"), events(4))
+            .await
+            .unwrap();
+
+        assert_eq!(receiver.len(), 2);
+        let spilled = queue
+            .store
+            .with_status(DeliveryStatus::Spilled, DRAIN_BATCH)
+            .await
+            .unwrap();
+        assert_eq!(spilled.len(), 1);
+        let payload: types::WebhookEvents = serde_json::from_str(&spilled[0].body).unwrap();
+        assert_eq!(payload.events, events(4)[2..]);
+        let queued = queue
+            .store
+            .with_status(DeliveryStatus::Queued, DRAIN_BATCH)
+            .await
+            .unwrap();
+        assert_eq!(queued.len(), 1);
+        let payload: types::WebhookEvents = serde_json::from_str(&queued[0].body).unwrap();
+        assert_eq!(payload.events, events(4)[..2]);
+
+        receiver.recv().await.unwrap();
+        receiver.recv().await.unwrap();
+        queue.drain().await.unwrap();
+        assert_eq!(receiver.len(), 2);
+        assert_eq!(queue.metrics().spilled, 0);
+    }
+
+    #[tokio::test]
+    async fn spills_whole_delivery_when_full() {
+        let (sender, _receiver) = mpsc::channel(1);
+        let queue = DeliveryQueue::new(sender, InMemoryDeliveryStore::default())
+            .await
+            .unwrap();
+        queue
+            .enqueue("1", String::from("{}"), events(1))
+            .await
+            .unwrap();
+        queue
+            .enqueue("1", String::from("{}"), events(3))
+            .await
+            .unwrap();
+
+        assert_eq!(queue.store.count(DeliveryStatus::Queued).await.unwrap(), 1);
+        assert_eq!(queue.store.count(DeliveryStatus::Spilled).await.unwrap(), 1);
+    }
+}
@@ -0,0 +1,286 @@
+//! Brings the webhooks in Asana in line with [`WebhookConfig`].
+//!
+//! Only webhooks delivering to our own target URL are considered; anything else in the
+//! workspace is left alone. Running it again with nothing changed is a no-op.
+
+use super::secrets::WebhookSecrets;
+use crate::{
+    asana::{self, types},
+    config::{ResourceKind, WatchedResource, WebhookConfig},
+    error::Error,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Resource gids affected by a reconcile run.
+#[derive(Debug, Default, Serialize)]
+pub struct ReconcileReport {
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub deleted: Vec<String>,
+    pub unchanged: Vec<String>,
+    pub failed: Vec<String>,
+}
+
+pub struct Reconciler<C> {
+    client: C,
+    secrets: WebhookSecrets,
+    config: WebhookConfig,
+}
+
+impl<C: asana::HTTPClient + Sync> Reconciler<C> {
+    pub fn new(client: C, secrets: WebhookSecrets, config: WebhookConfig) -> Self {
+        Self {
+            client,
+            secrets,
+            config,
+        }
+    }
+
+    /// Run a reconcile. Runs are serialized with each other and with the monitor's recreates.
+    pub async fn reconcile(&self) -> Result<ReconcileReport, Error> {
+        let _changes = self.secrets.lock_changes().await;
+        let mut report = ReconcileReport::default();
+
+        let mut existing: HashMap<String, Vec<types::WebhookData>> = HashMap::new();
+        for webhook in super::list_our_webhooks(&self.client, &self.config.workspace_gid).await? {
+            let Some(resource) = &webhook.resource else {
+                continue;
+            };
+            existing
+                .entry(resource.gid.clone())
+                .or_default()
+                .push(webhook);
+        }
+
+        for resource in &self.config.resources {
+            let webhooks = existing.remove(&resource.gid).unwrap_or_default();
+            match self.reconcile_resource(resource, webhooks).await {
+                Ok(Outcome::Created) => report.created.push(resource.gid.clone()),
+                Ok(Outcome::Updated) => report.updated.push(resource.gid.clone()),
+                Ok(Outcome::Unchanged) => report.unchanged.push(resource.gid.clone()),
+                Err(err) => {
+                    tracing::error!(
+                        "Failed to reconcile webhook for `gid: {}`: {}",
+                        resource.gid,
+                        err
+                    );
+                    report.failed.push(resource.gid.clone());
+                }
+            }
+        }
+
+        // Whatever is left delivers to us but is no longer wanted.
+        for (resource_gid, webhooks) in existing {
+            let result = async {
+                for webhook in &webhooks {
+                    asana::methods::delete_webhook(&self.client, &webhook.gid).await?;
+                }
+                self.secrets.forget(&resource_gid).await
+            };
+            match result.await {
+                Ok(()) => report.deleted.push(resource_gid),
+                Err(err) => {
+                    tracing::error!(
+                        "Failed to delete webhook for `gid: {}`: {}",
+                        resource_gid,
+                        err
+                    );
+                    report.failed.push(resource_gid);
+                }
+            }
+        }
+
+        tracing::info!("Webhook reconcile: {:?}", report);
+
+        Ok(report)
+    }
+
+    async fn reconcile_resource(
+        &self,
+        resource: &WatchedResource,
+        mut webhooks: Vec<types::WebhookData>,
+    ) -> Result<Outcome, Error> {
+        if resource.kind == ResourceKind::Workspace && resource.filters.is_empty() {
+            return Err(Error::new(format!(
+                "Workspace webhook for `gid: {}` requires at least one filter",
+                resource.gid
+            )));
+        }
+
+        // Asana allows several webhooks per resource and target; keep only the first.
+        let duplicates = webhooks.split_off(webhooks.len().min(1));
+        for duplicate in duplicates {
+            asana::methods::delete_webhook(&self.client, &duplicate.gid).await?;
+        }
+
+        let Some(webhook) = webhooks.pop() else {
+            self.secrets
+                .create_webhook(&self.client, &resource.gid, &resource.filters)
+                .await?;
+
+            return Ok(Outcome::Created);
+        };
+
+        // Without its handshake secret no delivery from this webhook could be verified.
+        if self.secrets.get(&resource.gid).await?.is_none() {
+            if self.secrets.is_persistent() {
+                tracing::warn!(
+                    "No handshake secret for webhook `gid: {}` on `gid: {}`, recreating it",
+                    webhook.gid,
+                    resource.gid
+                );
+            } else {
+                tracing::warn!(
+                    "Recreating webhook `gid: {}` on `gid: {}`: secrets are held in memory, so \
+                     every restart recreates every webhook; set DATABASE_URL to keep them",
+                    webhook.gid,
+                    resource.gid
+                );
+            }
+            asana::methods::delete_webhook(&self.client, &webhook.gid).await?;
+            self.secrets
+                .create_webhook(&self.client, &resource.gid, &resource.filters)
+                .await?;
+
+            return Ok(Outcome::Created);
+        }
+
+        let outcome = if webhook.filters == resource.filters {
+            Outcome::Unchanged
+        } else {
+            asana::methods::update_webhook(&self.client, &webhook.gid, &resource.filters).await?;
+            Outcome::Updated
+        };
+        self.secrets
+            .link_webhook(&resource.gid, &webhook.gid, &resource.filters)
+            .await?;
+
+        Ok(outcome)
+    }
+}
+
+enum Outcome {
+    Created,
+    Updated,
+    Unchanged,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use serde_json::{json, Value};
+    use std::sync::Mutex;
+
+    const RESOURCE: &str = "1200";
+
+    /// Lists and creates webhooks in memory, yielding mid-request so concurrent runs interleave.
+    /// Lists omit `filters` unless requested through `opt_fields`, as Asana's compact records do.
+    #[derive(Default)]
+    struct Asana {
+        secrets: Option<WebhookSecrets>,
+        webhooks: Mutex<Vec<Value>>,
+    }
+
+    #[async_trait]
+    impl asana::HTTPClient for Asana {
+        async fn send_request<T>(
+            &self,
+            method: &str,
+            _endpoint: &str,
+            params: &[(&str, &str)],
+            body: Option<String>,
+        ) -> Result<(T, Value), Error>
+        where
+            T: serde::de::DeserializeOwned + std::fmt::Debug,
+        {
+            tokio::task::yield_now().await;
+            let value = if method == "GET" {
+                let with_filters = params.iter().any(|(key, value)| {
+                    *key == "opt_fields" && value.split(',').any(|field| field == "filters")
+                });
+                let mut webhooks = self.webhooks.lock().unwrap().clone();
+                if !with_filters {
+                    for webhook in &mut webhooks {
+                        webhook.as_object_mut().unwrap().remove("filters");
+                    }
+                }
+                json!({ "data": webhooks, "next_page": null })
+            } else if method == "PUT" {
+                return Err(Error::new("Request Failure: Status: 400 Bad Request"));
+            } else {
+                if let Some(secrets) = &self.secrets {
+                    secrets.record_handshake(RESOURCE, "secret").await?;
+                }
+                let mut webhooks = self.webhooks.lock().unwrap();
+                let webhook = json!({
+                    "gid": (9000 + webhooks.len()).to_string(),
+                    "resource_type": "webhook",
+                    "resource": { "gid": RESOURCE, "resource_type": "project" },
+                    "target": asana::methods::webhook_target(RESOURCE),
+                    "filters": body
+                        .and_then(|body| serde_json::from_str::<Value>(&body).ok())
+                        .and_then(|body| body["data"].get("filters").cloned())
+                        .unwrap_or_else(|| json!([])),
+                });
+                webhooks.push(webhook.clone());
+                json!({ "data": webhook })
+            };
+
+            Ok((serde_json::from_value(value.clone())?, value))
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_runs_create_one_webhook() {
+        let secrets = WebhookSecrets::in_memory();
+        let client = Asana {
+            secrets: Some(secrets.clone()),
+            ..Default::default()
+        };
+        let config = WebhookConfig {
+            workspace_gid: String::from("1"),
+            resources: vec![WatchedResource {
+                gid: RESOURCE.to_string(),
+                kind: ResourceKind::Project,
+                filters: Vec::new(),
+            }],
+        };
+        let reconciler = Reconciler::new(client, secrets, config);
+
+        let (first, second) = tokio::join!(reconciler.reconcile(), reconciler.reconcile());
+        first.unwrap();
+        second.unwrap();
+
+        assert_eq!(reconciler.client.webhooks.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn unchanged_filters_leave_webhook_alone() {
+        let secrets = WebhookSecrets::in_memory();
+        let client = Asana {
+            secrets: Some(secrets.clone()),
+            ..Default::default()
+        };
+        let filters = serde_json::from_value(json!([
+            { "resource_type": "task", "action": "changed", "fields": ["name", "due_on"] },
+        ]))
+        .unwrap();
+        let config = WebhookConfig {
+            workspace_gid: String::from("1"),
+            resources: vec![WatchedResource {
+                gid: RESOURCE.to_string(),
+                kind: ResourceKind::Project,
+                filters,
+            }],
+        };
+        let reconciler = Reconciler::new(client, secrets, config);
+
+        assert_eq!(reconciler.reconcile().await.unwrap().created, [RESOURCE]);
+        let report = reconciler.reconcile().await.unwrap();
+        assert_eq!(report.unchanged, [RESOURCE]);
+        assert!(report.failed.is_empty());
+        assert_eq!(reconciler.client.webhooks.lock().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,472 @@
+//! Handshake secrets for our webhooks.
+//!
+//! Asana sends `X-Hook-Secret` to the target URL while the create-webhook request is still in
+//! flight, before we know the webhook's gid. The secret is therefore held as pending against the
+//! resource gid in the target path, and only replaces the current secret once `create_webhook`
+//! returns.
+//!
+//! Handshakes are only accepted while we are creating a webhook for that resource: the record is
+//! written with a deadline before the request is sent, and any other handshake is rejected.
+
+use crate::{asana, error::Error};
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use mongodb::{
+    bson::{self, doc},
+    Collection, Database,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::sync::{Mutex, MutexGuard, RwLock};
+
+const COLLECTION: &str = "webhook_secrets";
+/// How long after sending a create-webhook request its handshake is accepted.
+const HANDSHAKE_WINDOW: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookSecret {
+    #[serde(rename = "_id")]
+    pub resource_gid: String,
+    /// Unset until the first webhook for this resource has been created.
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// Secret from the latest handshake of a create still in flight. Promoted to `secret` once the
+    /// create succeeds, discarded if it fails.
+    #[serde(default)]
+    pub pending_secret: Option<String>,
+    /// Set once `create_webhook` has returned for the handshake that produced `secret`.
+    pub webhook_gid: Option<String>,
+    /// Filters the webhook was created with, so it can be recreated identically.
+    #[serde(default)]
+    pub filters: Vec<asana::types::Filter>,
+    /// A webhook is being created for this resource, so a handshake received before this deadline
+    /// may set `pending_secret`.
+    #[serde(default)]
+    pub handshake_until: Option<bson::DateTime>,
+    pub updated_at: bson::DateTime,
+}
+
+/// Storage backend for [`WebhookSecret`] records.
+#[async_trait]
+pub trait SecretStore: Send + Sync {
+    async fn get(&self, resource_gid: &str) -> Result<Option<WebhookSecret>, Error>;
+    async fn put(&self, secret: &WebhookSecret) -> Result<(), Error>;
+    async fn list(&self) -> Result<Vec<WebhookSecret>, Error>;
+    async fn delete(&self, resource_gid: &str) -> Result<(), Error>;
+
+    /// Whether secrets survive a restart.
+    fn is_persistent(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct InMemorySecretStore {
+    inner: RwLock<HashMap<String, WebhookSecret>>,
+}
+
+#[async_trait]
+impl SecretStore for InMemorySecretStore {
+    async fn get(&self, resource_gid: &str) -> Result<Option<WebhookSecret>, Error> {
+        Ok(self.inner.read().await.get(resource_gid).cloned())
+    }
+
+    async fn put(&self, secret: &WebhookSecret) -> Result<(), Error> {
+        self.inner
+            .write()
+            .await
+            .insert(secret.resource_gid.clone(), secret.clone());
+
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<WebhookSecret>, Error> {
+        Ok(self.inner.read().await.values().cloned().collect())
+    }
+
+    async fn delete(&self, resource_gid: &str) -> Result<(), Error> {
+        self.inner.write().await.remove(resource_gid);
+
+        Ok(())
+    }
+
+    fn is_persistent(&self) -> bool {
+        false
+    }
+}
+
+pub struct MongoSecretStore {
+    collection: Collection<WebhookSecret>,
+}
+
+impl MongoSecretStore {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            collection: db.collection(COLLECTION),
+        }
+    }
+}
+
+#[async_trait]
+impl SecretStore for MongoSecretStore {
+    async fn get(&self, resource_gid: &str) -> Result<Option<WebhookSecret>, Error> {
+        Ok(self
+            .collection
+            .find_one(doc! { "_id": resource_gid })
+            .await?)
+    }
+
+    async fn put(&self, secret: &WebhookSecret) -> Result<(), Error> {
+        self.collection
+            .replace_one(doc! { "_id": &secret.resource_gid }, secret)
+            .upsert(true)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<WebhookSecret>, Error> {
+        let cursor = self.collection.find(doc! {}).await?;
+
+        Ok(cursor.try_collect().await?)
+    }
+
+    async fn delete(&self, resource_gid: &str) -> Result<(), Error> {
+        self.collection
+            .delete_one(doc! { "_id": resource_gid })
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Shared handle to the configured [`SecretStore`].
+#[derive(Clone)]
+pub struct WebhookSecrets {
+    store: Arc<dyn SecretStore>,
+    changes: Arc<Mutex<()>>,
+}
+
+impl WebhookSecrets {
+    pub fn new(store: impl SecretStore + 'static) -> Self {
+        Self {
+            store: Arc::new(store),
+            changes: Arc::default(),
+        }
+    }
+
+    pub fn in_memory() -> Self {
+        Self::new(InMemorySecretStore::default())
+    }
+
+    pub fn is_persistent(&self) -> bool {
+        self.store.is_persistent()
+    }
+
+    /// Held while creating, replacing or deleting webhooks, so that concurrent reconcile runs
+    /// and monitor checks cannot each create a webhook for the same resource.
+    pub async fn lock_changes(&self) -> MutexGuard<'_, ()> {
+        self.changes.lock().await
+    }
+
+    /// Every recorded secret, including those not yet linked to a webhook.
+    pub async fn list(&self) -> Result<Vec<WebhookSecret>, Error> {
+        self.store.list().await
+    }
+
+    pub async fn record(&self, resource_gid: &str) -> Result<Option<WebhookSecret>, Error> {
+        self.store.get(resource_gid).await
+    }
+
+    pub async fn get(&self, resource_gid: &str) -> Result<Option<String>, Error> {
+        Ok(self
+            .store
+            .get(resource_gid)
+            .await?
+            .and_then(|record| record.secret))
+    }
+
+    /// Record the secret from a handshake as pending. Returns `false` unless
+    /// [`Self::create_webhook`] is awaiting a handshake for this resource. The window stays open
+    /// until the create returns, so a forged handshake can neither replace the current secret nor
+    /// shut out the real handshake, which overwrites it.
+    pub async fn record_handshake(&self, resource_gid: &str, secret: &str) -> Result<bool, Error> {
+        let Some(mut record) = self.store.get(resource_gid).await? else {
+            return Ok(false);
+        };
+        let now = bson::DateTime::now();
+        if !record.handshake_until.is_some_and(|until| now < until) {
+            return Ok(false);
+        }
+
+        record.pending_secret = Some(secret.to_string());
+        record.updated_at = now;
+        self.store.put(&record).await?;
+
+        Ok(true)
+    }
+
+    /// Link the secret to the webhook Asana returned, promoting the pending handshake secret if a
+    /// create just completed.
+    pub async fn link_webhook(
+        &self,
+        resource_gid: &str,
+        webhook_gid: &str,
+        filters: &[asana::types::Filter],
+    ) -> Result<(), Error> {
+        let record = self.store.get(resource_gid).await?;
+        let Some(mut record) = record.filter(|r| r.secret.is_some() || r.pending_secret.is_some())
+        else {
+            return Err(Error::new(format!(
+                "No handshake secret recorded for resource `gid: {}`",
+                resource_gid
+            )));
+        };
+        if let Some(secret) = record.pending_secret.take() {
+            record.secret = Some(secret);
+        }
+        record.handshake_until = None;
+        record.webhook_gid = Some(webhook_gid.to_string());
+        record.filters = filters.to_vec();
+        record.updated_at = bson::DateTime::now();
+
+        self.store.put(&record).await
+    }
+
+    /// Drop the secret for a resource whose webhook has been deleted on purpose, so the monitor
+    /// does not recreate it and a later handshake starts afresh.
+    pub async fn forget(&self, resource_gid: &str) -> Result<(), Error> {
+        self.store.delete(resource_gid).await
+    }
+
+    /// Accept a handshake for `resource_gid` until [`HANDSHAKE_WINDOW`] has passed. Any current
+    /// secret stays valid until [`Self::link_webhook`] replaces it.
+    async fn expect_handshake(&self, resource_gid: &str) -> Result<(), Error> {
+        let now = bson::DateTime::now();
+        let mut record = self
+            .store
+            .get(resource_gid)
+            .await?
+            .unwrap_or_else(|| WebhookSecret {
+                resource_gid: resource_gid.to_string(),
+                secret: None,
+                pending_secret: None,
+                webhook_gid: None,
+                filters: Vec::new(),
+                handshake_until: None,
+                updated_at: now,
+            });
+        record.handshake_until = Some(bson::DateTime::from_millis(
+            now.timestamp_millis() + HANDSHAKE_WINDOW.as_millis() as i64,
+        ));
+        record.updated_at = now;
+
+        self.store.put(&record).await
+    }
+
+    /// Stop accepting handshakes after a failed create and discard any pending secret, dropping
+    /// the record if no create has ever succeeded for this resource.
+    async fn cancel_handshake(&self, resource_gid: &str) -> Result<(), Error> {
+        let Some(mut record) = self.store.get(resource_gid).await? else {
+            return Ok(());
+        };
+        if record.secret.is_none() {
+            return self.store.delete(resource_gid).await;
+        }
+        record.pending_secret = None;
+        record.handshake_until = None;
+        record.updated_at = bson::DateTime::now();
+
+        self.store.put(&record).await
+    }
+
+    /// Create a webhook on `resource_gid` and link the handshake secret to it, rotating any
+    /// secret held from a previous webhook on the same resource.
+    pub async fn create_webhook(
+        &self,
+        client: &impl asana::HTTPClient,
+        resource_gid: &str,
+        filters: &[asana::types::Filter],
+    ) -> Result<asana::methods::WebhookResponse, Error> {
+        self.expect_handshake(resource_gid).await?;
+
+        let created = match asana::methods::create_webhook(client, resource_gid, filters).await {
+            Ok((webhook, _)) => self
+                .link_webhook(resource_gid, &webhook.data.gid, filters)
+                .await
+                .map(|()| webhook),
+            Err(err) => Err(err),
+        };
+        if created.is_err() {
+            if let Err(err) = self.cancel_handshake(resource_gid).await {
+                tracing::error!(
+                    "Failed to close the handshake window for `gid: {}`: {}",
+                    resource_gid,
+                    err
+                );
+            }
+        }
+
+        created
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    const RESOURCE: &str = "1200";
+
+    /// Answers `POST /webhooks` after performing each of `handshakes`, failing if `respond` is
+    /// unset or no handshake was accepted.
+    struct Asana {
+        secrets: WebhookSecrets,
+        handshakes: &'static [&'static str],
+        respond: bool,
+    }
+
+    #[async_trait]
+    impl asana::HTTPClient for Asana {
+        async fn send_request<T>(
+            &self,
+            _method: &str,
+            _endpoint: &str,
+            _params: &[(&str, &str)],
+            _body: Option<String>,
+        ) -> Result<(T, Value), Error>
+        where
+            T: serde::de::DeserializeOwned + std::fmt::Debug,
+        {
+            let mut accepted = false;
+            for secret in self.handshakes {
+                accepted |= self.secrets.record_handshake(RESOURCE, secret).await?;
+            }
+            if !accepted || !self.respond {
+                return Err(Error::new("Request Failure: Status: 400 Bad Request"));
+            }
+            let value = json!({ "data": { "gid": "9000", "resource_type": "webhook" } });
+
+            Ok((serde_json::from_value(value.clone())?, value))
+        }
+    }
+
+    async fn create(
+        secrets: &WebhookSecrets,
+        handshakes: &'static [&'static str],
+        respond: bool,
+    ) -> Result<(), Error> {
+        let client = Asana {
+            secrets: secrets.clone(),
+            handshakes,
+            respond,
+        };
+
+        secrets
+            .create_webhook(&client, RESOURCE, &[])
+            .await
+            .map(|_| ())
+    }
+
+    #[tokio::test]
+    async fn rejects_unsolicited_handshake() {
+        let secrets = WebhookSecrets::in_memory();
+
+        assert!(!secrets.record_handshake(RESOURCE, "forged").await.unwrap());
+        assert!(secrets.list().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn links_handshake_during_create() {
+        let secrets = WebhookSecrets::in_memory();
+        create(&secrets, &["first"], true).await.unwrap();
+
+        let record = secrets.store.get(RESOURCE).await.unwrap().unwrap();
+        assert_eq!(record.secret.as_deref(), Some("first"));
+        assert_eq!(record.webhook_gid.as_deref(), Some("9000"));
+        assert!(record.pending_secret.is_none());
+        assert!(record.handshake_until.is_none());
+        assert!(!secrets.record_handshake(RESOURCE, "forged").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn forged_handshake_does_not_shut_out_real_one() {
+        let secrets = WebhookSecrets::in_memory();
+        create(&secrets, &["forged", "real"], true).await.unwrap();
+
+        assert_eq!(
+            secrets.get(RESOURCE).await.unwrap().as_deref(),
+            Some("real")
+        );
+    }
+
+    #[tokio::test]
+    async fn rotates_secret_on_recreate() {
+        let secrets = WebhookSecrets::in_memory();
+        create(&secrets, &["first"], true).await.unwrap();
+        create(&secrets, &["second"], true).await.unwrap();
+
+        assert_eq!(
+            secrets.get(RESOURCE).await.unwrap().as_deref(),
+            Some("second")
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_create_closes_window() {
+        let secrets = WebhookSecrets::in_memory();
+        assert!(create(&secrets, &[], true).await.is_err());
+
+        assert!(secrets.list().await.unwrap().is_empty());
+        assert!(!secrets.record_handshake(RESOURCE, "forged").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn failed_recreate_keeps_secret() {
+        let secrets = WebhookSecrets::in_memory();
+        create(&secrets, &["first"], true).await.unwrap();
+        assert!(create(&secrets, &[], true).await.is_err());
+
+        assert_eq!(
+            secrets.get(RESOURCE).await.unwrap().as_deref(),
+            Some("first")
+        );
+        assert!(!secrets.record_handshake(RESOURCE, "forged").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn failed_create_after_handshake_keeps_secret() {
+        let secrets = WebhookSecrets::in_memory();
+        create(&secrets, &["first"], true).await.unwrap();
+        assert!(create(&secrets, &["second"], false).await.is_err());
+
+        assert_eq!(
+            secrets.get(RESOURCE).await.unwrap().as_deref(),
+            Some("first")
+        );
+        let record = secrets.store.get(RESOURCE).await.unwrap().unwrap();
+        assert!(record.pending_secret.is_none());
+        assert!(!secrets.record_handshake(RESOURCE, "forged").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn rejects_handshake_after_deadline() {
+        let secrets = WebhookSecrets::in_memory();
+        let now = bson::DateTime::now();
+        secrets
+            .store
+            .put(&WebhookSecret {
+                resource_gid: RESOURCE.to_string(),
+                secret: None,
+                pending_secret: None,
+                webhook_gid: None,
+                filters: Vec::new(),
+                handshake_until: Some(bson::DateTime::from_millis(now.timestamp_millis() - 1)),
+                updated_at: now,
+            })
+            .await
+            .unwrap();
+
+        assert!(!secrets.record_handshake(RESOURCE, "late").await.unwrap());
+    }
+}
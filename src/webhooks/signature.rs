@@ -0,0 +1,57 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Check an `X-Hook-Signature` value against `body`. The comparison is constant time so a
+/// mismatch leaks nothing about how much of the signature was correct.
+pub fn verify(secret: &str, body: &[u8], signature: &str) -> bool {
+    let Ok(signature) = hex::decode(signature.trim()) else {
+        return false;
+    };
+
+    digest(secret, body).as_slice().ct_eq(&signature).into()
+}
+
+fn digest(secret: &str, body: &[u8]) -> Vec<u8> {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+
+    mac.finalize().into_bytes().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "secret";
+    const BODY: &[u8] = br#"{"events":[]}"#;
+    const SIGNATURE: &str = "a642b59553c93e227ec0f2f38910fbf71231a2197c00899833c00478cec86f34";
+
+    #[test]
+    fn accepts_valid_signature() {
+        assert!(verify(SECRET, BODY, SIGNATURE));
+        assert!(verify(SECRET, BODY, &SIGNATURE.to_uppercase()));
+    }
+
+    #[test]
+    fn rejects_wrong_secret_or_body() {
+        assert!(!verify("other", BODY, SIGNATURE));
+        assert!(!verify(SECRET, br#"{"events":[{}]}"#, SIGNATURE));
+    }
+
+    #[test]
+    fn rejects_bad_hex() {
+        let signature = format!("{}zz", &SIGNATURE[..62]);
+        assert!(!verify(SECRET, BODY, &signature));
+        assert!(!verify(SECRET, BODY, ""));
+    }
+
+    #[test]
+    fn rejects_wrong_length() {
+        assert!(!verify(SECRET, BODY, &SIGNATURE[..62]));
+        assert!(!verify(SECRET, BODY, &format!("{}00", SIGNATURE)));
+    }
+}